@@ -1,3 +1,269 @@
+// GHOST Protocol v1.11 — UNRELEASED
+// Changes from v1.10:
+//   1. Native SOL vault. The vault PDA ["vault", owner] can now hold lamports directly:
+//      `deposit_sol_to_vault`, `withdraw_sol_from_vault`, `execute_sol_transfer` and
+//      `execute_whole_vault_sol_transfer`. A beneficiary with `token_mint = None` is a
+//      native SOL slot (Transfer only — SOL cannot be burned). The vault keeps its
+//      rent-exempt reserve; only lamports above it are distributable.
+//   2. Percentage beneficiaries. New `beneficiary_ext: [BeneficiaryExt; 10]` array appended
+//      to GhostAccount (parallel to `beneficiaries`, same indices). `set_beneficiary_share`
+//      puts a slot in share mode (basis points of the vault balance for its mint). Shares
+//      for one mint can never exceed 10,000 bps. `execute_legacy` resolves every share slot
+//      into an absolute `amount` against the vault balances it reads in that same
+//      instruction, so payouts stay deterministic. Schema bump → SCHEMA_VERSION_V111;
+//      share mode requires a migrated account.
+//   3. Execution snapshot. `execute_legacy` now creates an `ExecutionSnapshot` PDA
+//      ["snapshot", ghost] recording, per beneficiary mint, the vault balance and the total
+//      committed to slots. Every payout is computed against those frozen numbers: a fully
+//      funded mint pays `amount`, a short mint pays `amount * balance / committed` to every
+//      slot regardless of keeper call order. Whole-vault sweeps only take the balance above
+//      what unpaid slots are still owed. `take_execution_snapshot` backfills the snapshot
+//      for ghosts executed before v1.11. Token balances are only read from the vault's
+//      canonical ATA for each mint, so a decoy vault-owned account can't freeze a 0.
+//   4. Residual recipient. New `residual_recipient: Option<Pubkey>` on GhostAccount, set via
+//      `set_residual_recipient`. Once every beneficiary slot is executed (trivially true when
+//      beneficiary_count == 0) and no whole_vault_recipient is set, `execute_residual_sweep`
+//      / `execute_residual_sol_sweep` move whatever is left of any mint to it.
+//   5. migrate_ghost now takes the ghost as a raw account and reallocs BEFORE deserializing.
+//      The v1.11 struct can exceed the old 1221/1229-byte allocation for accounts with every
+//      Option populated, which made `Account<GhostAccount>` fail before migration could run.
+//      Once a ghost has executed, migrate_ghost is permissionless, so a ghost whose owner died
+//      before migrating still reaches the residual sweep. Its residual_recipient then
+//      defaults to the owner's wallet.
+//   6. Pull-based claims. `claim_beneficiary` lets the beneficiary sign for their own token
+//      slot: their ATA and the fee wallet's ATA are created on the spot (`init_if_needed`,
+//      paid by them) and the slot pays exactly as execute_transfer would. No keeper needed.
+//      SOL slots need no ATA — the beneficiary can call execute_sol_transfer directly.
+//   7. M-of-N guardian quorum. New `guardian_threshold: u8` and `proposal_count: u64` on
+//      GhostAccount. With a threshold above 1, the direct guardian instructions
+//      (recovery_withdraw, guardian_remove_beneficiary, guardian_clear_beneficiaries,
+//      guardian_set_whole_vault_recipient) are rejected; guardians instead
+//      `propose_guardian_action` → `approve_guardian_action` → `execute_guardian_action`
+//      through a `GuardianProposal` PDA ["proposal", ghost, id]. Proposals expire after
+//      PROPOSAL_TTL_SECONDS and are closed by `expire_guardian_proposal`. A proposal records
+//      the recovery wallet set it was created under and becomes unexecutable if it changes.
+//   8. Guardian time-lock with owner veto. New `guardian_delay_seconds`, `owner_ping_nonce`
+//      and `queued_guardian_actions` on GhostAccount. A proposal that reaches quorum is queued
+//      and only executable `guardian_delay_seconds` later. Until then the owner can veto it
+//      with `veto_guardian_action`, or veto everything queued with a plain `ping` (which bumps
+//      owner_ping_nonce; proposals remember the nonce they were opened under). Any non-zero
+//      delay also disables the direct single-guardian instructions.
+//   9. Ownership transfer restored (reverses v1.10 item 2). New `original_owner: Pubkey` and
+//      `pending_owner_since: i64` on GhostAccount. Ghost and vault PDAs are now derived from
+//      `ghost.seed_owner()` — original_owner once migrated, owner before that (identical on
+//      every unmigrated account since owner could not change). `transfer_ownership` opens a
+//      transfer to `pending_owner`; the new key calls `accept_ownership` after
+//      OWNERSHIP_TRANSFER_COOLDOWN_SECONDS. The current owner can `cancel_ownership_transfer`
+//      any time before acceptance. Acceptance clears the previous owner's ping delegates
+//      and vetoes every queued guardian action, as an owner ping would.
+//  10. Ping delegates. New `ping_delegates: [PingDelegate; 3]` on GhostAccount. The owner
+//      registers hot keys (phone, bot wallet) with an expiry via `add_ping_delegate` /
+//      `remove_ping_delegate`. A live delegate can sign `ping` and nothing else. Delegate
+//      pings keep the ghost alive and cancel an awakening, but only an owner ping vetoes
+//      queued guardian actions.
+//  11. Relayed signed heartbeats. `ping_with_signature(nonce, timestamp)` may be submitted by
+//      anyone. The transaction must place an Ed25519 sig-verify instruction immediately before
+//      it, over the 48-byte message ghost PDA ‖ nonce (u64 LE) ‖ timestamp (i64 LE), signed
+//      by the owner. `nonce` must equal the new `heartbeat_nonce` field (then incremented).
+//      The heartbeat is recorded at the signed timestamp, which must be no older than
+//      SIGNED_PING_MAX_AGE_SECONDS and later than awakened_at (or, if not awakened, the
+//      last heartbeat). It counts as an owner ping.
+//  12. Multiple ghosts per wallet. New `ghost_index: u16` and `indexed: bool` on GhostAccount.
+//      `initialize_indexed_ghost(ghost_index, ..)` creates ["ghost", owner, index LE],
+//      ["vault", owner, index LE] and ["stake_vault", owner, index LE]. Every Accounts struct
+//      appends `ghost.index_seed()` to the ghost/vault seeds — empty for legacy ghosts, so
+//      their addresses are unchanged. The shared init body moved to GhostAccount::initialize.
+//  13. Beneficiary accounts. Beyond the 10 embedded slots, the owner can create any number
+//      of `BeneficiaryAccount` PDAs ["beneficiary", ghost, id u32 LE] with
+//      `add_beneficiary_account` / `update_beneficiary_account` / `remove_beneficiary_account`.
+//      GhostAccount gains `beneficiary_accounts` (live count) and `next_beneficiary_id`.
+//      There are too many to pass to execute_legacy, so the snapshot records
+//      `pending_tallies` and each account is folded in by the permissionless
+//      `tally_beneficiary_account` crank. No payout or sweep runs until every account is
+//      tallied, so pro-rata entitlements are final before anything moves.
+//      execute_transfer / execute_burn take an optional `beneficiary_account`. When it is
+//      supplied, it is the slot being paid and `beneficiary_index` is ignored. Beneficiary
+//      accounts are absolute-amount only (no share mode), and guardian beneficiary actions
+//      still cover only the embedded slots. GhostAccount also gains `account_mints`, a
+//      per-mint count of beneficiary accounts. Adding or updating any beneficiary is refused
+//      once it would take the ghost past MAX_SNAPSHOT_MINTS distinct mints, because the
+//      snapshot could not tally it.
+//  14. Vesting. BeneficiaryExt gains `vesting_cliff_seconds`, `vesting_duration_seconds`,
+//      `vesting_tranches` and `claimed`, set with `set_beneficiary_vesting` (token Transfer
+//      slots only). Vesting runs from `executed_at`: nothing before the cliff, then linear
+//      over the duration (tranches = 0) or in equal steps. `claim_vested` is permissionless
+//      and pays the unlocked part of the slot's snapshot entitlement minus what was already
+//      claimed. The slot is marked executed once fully claimed. execute_transfer and
+//      claim_beneficiary reject vesting slots.
+//  15. Contingent beneficiaries. BeneficiaryExt gains `contingent_recipient` and
+//      `contingent_after_seconds`, set with `set_beneficiary_contingent` (token Transfer slots,
+//      not vesting). Such a slot is pull-only for the primary: execute_transfer rejects it,
+//      and the primary must sign claim_beneficiary. This proves they still hold the key.
+//      Once executed_at + contingent_after_seconds has passed, anyone may call
+//      `execute_contingent_transfer` to pay the contingent recipient instead. Whichever
+//      payment lands first executes the slot.
+//  16. Disclaimers. After execution, a slot's recipient can sign `disclaim_beneficiary` to
+//      renounce it (token or SOL Transfer slots, nothing claimed yet). A slot with a
+//      contingent recipient becomes payable to them immediately. Otherwise the new
+//      `disclaim_policy` on GhostAccount (set with `set_disclaim_policy`) decides where it goes.
+//      DISCLAIM_POLICY_ESTATE marks the slot executed, so its amount is released to the
+//      whole-vault / residual sweep. DISCLAIM_POLICY_PRO_RATA adds it to the other unpaid
+//      embedded Transfer slots of the same mint, in proportion to their amounts. If there are
+//      none, it falls back to the estate. BeneficiaryExt gains `disclaimed`.
+//  17. Multi-mint whole-vault sweep. `execute_whole_vault_sweep` takes groups of
+//      [vault_token_account, mint, recipient_token_account, fee_token_account] in
+//      remaining_accounts, all under one token program. Each mint is swept exactly like
+//      execute_whole_vault_transfer. A vault token account with nothing left reserved for
+//      unpaid slots is then closed, and its rent goes to the recipient. Both whole-vault
+//      token instructions record every sweep in the snapshot's new `swept` list (mint, gross
+//      amount, closed), so which mints have been swept is readable on-chain.
+//  18. Finalization. New `rent_recipient: Option<Pubkey>` on GhostAccount (`set_rent_recipient`).
+//      When it is unset, rent goes to the estate recipient (a Transfer
+//      whole_vault_recipient, then residual_recipient), and failing those to the owner.
+//      Once every beneficiary is settled, these permissionless instructions tear the ghost
+//      down, with all rent going there:
+//      `close_vault_token_account` closes one empty vault token account and records it in
+//      the snapshot's `swept` list, as do the whole-vault and residual sweeps.
+//      `close_beneficiary_account` closes a paid BeneficiaryAccount. `finalize_ghost`
+//      takes the vault token accounts still open as remaining_accounts pairs of
+//      [vault_token_account, token_program], and closes each one, which must be empty.
+//      After that every mint in the snapshot must be closed and no beneficiary accounts
+//      may be left. It then pays the remaining stake to the rent recipient's $GHOST
+//      account and closes the stake vault, the snapshot and the GhostAccount. The vault
+//      PDA's lamport reserve goes with them.
+//  19. Protocol config. The execution fee, registration fee, minimum stake, fee wallet and
+//      bot ops wallet now live in a singleton `ProtocolConfig` PDA ["config"]. The program
+//      upgrade authority creates it once with `initialize_protocol_config`, seeded from the
+//      old constants, which are now only those initial values. After that its `admin` can
+//      call `update_protocol_fees` (execution fee capped at MAX_EXECUTION_FEE_BPS,
+//      registration fee at MAX_REGISTRATION_FEE_LAMPORTS, minimum stake at MAX_MIN_STAKE,
+//      minimum SOL deposit at MAX_MIN_SOL_DEPOSIT_LAMPORTS), `update_protocol_wallets` and
+//      `set_protocol_admin`. Every fee-taking instruction takes the config account and
+//      reads its values.
+//  20. Fee lock-in. New `execution_fee_bps: u16` on GhostAccount, copied from ProtocolConfig
+//      at registration. Migrated accounts get EXECUTION_FEE_BPS, the rate they registered
+//      under. Every fee-taking instruction charges `ghost.fee_bps(config)`, the lower of
+//      the locked rate and the current config rate, and stores that lower rate back. A
+//      config change can lower a ghost's fee but never raise it above what the owner agreed
+//      to, nor back up after a later config increase.
+//  21. SOL-deposit registration. `initialize_sol_ghost(interval, grace, deposit_lamports)`
+//      registers without $GHOST. The registration fee is still charged, and at least
+//      config.min_sol_deposit lamports are locked in the ghost PDA on top of its rent. New
+//      `sol_staked: bool` and `sol_deposit: u64` on GhostAccount. check_silence pays the
+//      bounty (SILENCE_BOUNTY_BPS of the deposit) in lamports, and GhostAwakened.bounty_paid
+//      is then lamports. abandon_ghost refunds the whole deposit with the account's rent.
+//      The stake accounts of check_silence, abandon_ghost and finalize_ghost are optional,
+//      and None is passed for SOL-deposit ghosts.
+//  22. Abandon guards. abandon_ghost is refused while awakened or executed, and while any
+//      BeneficiaryAccount is still open. Before the GhostAccount closes, it empties the
+//      vault to the owner. remaining_accounts carries groups of [vault_token_account, mint,
+//      owner_token_account, token_program]. Each account is withdrawn in full and closed,
+//      and every lamport of the vault PDA is returned. A vault token account left out
+//      stays open under the vault PDA; only re-registering the same ghost address reaches
+//      it again. Only the registering key can do that, so a ghost whose ownership was
+//      transferred cannot be abandoned. The $GHOST stake vault is closed too, with its
+//      rent going to the owner.
+//  23. Abandon trial window. ProtocolConfig gains `abandon_trial_seconds` and
+//      `abandon_burn_ramp_seconds` (initially ABANDON_TRIAL_SECONDS / ABANDON_BURN_RAMP_SECONDS),
+//      set by the admin via `update_abandon_schedule`. Within the trial window after
+//      `registered_at`, abandon_ghost returns the whole stake. After it the burn rises
+//      linearly over the ramp, up to BURN_ON_ABANDON_BPS. GhostAbandoned gains `burn_bps` (u16).
+//  24. Vacation mode. `pause_ghost(until)` requires an end time, at most
+//      config.max_pause_seconds ahead (initially MAX_PAUSE_SECONDS, admin-set via
+//      `update_max_pause` up to MAX_PAUSE_SECONDS_CAP). New `paused_until: i64` on
+//      GhostAccount. Once it passes, check_silence works again, with silence counted from
+//      max(last_heartbeat, paused_until). The stale flag is cleared by check_silence or by
+//      the permissionless `expire_pause`, and both emit GhostPauseLapsed. resume_ghost
+//      ends the pause early. migrate_ghost gives an already-paused ghost MAX_PAUSE_SECONDS
+//      from migration. Beneficiary and settings instructions refuse only a live pause, so a
+//      lapsed one no longer blocks them.
+//  25. Multi-stage awakening. `set_awakening_stages(warning_bps, final_notice_bps)` adds up
+//      to two stages around check_silence. "Warning" comes after warning_bps of the interval
+//      of silence, and "final notice" after final_notice_bps of the grace period. 0 disables
+//      a stage. Permissionless `issue_warning` / `issue_final_notice` record them
+//      (`warned_at`, `final_notice_at`) and emit GhostWarned / GhostFinalNotice. An enabled
+//      stage is required before the next step: warning before check_silence, final notice
+//      before execute_legacy. issue_final_notice pays its caller FINAL_NOTICE_BOUNTY_BPS and
+//      check_silence pays the rest of SILENCE_BOUNTY_BPS, both of `stage_bounty_base` (new
+//      on GhostAccount), the stake check_silence found. So the total stays the same. The
+//      warning pays nothing itself: a heartbeat after it means no awakening, so its bounty
+//      stays with the check_silence caller. A heartbeat or cancelled awakening resets the
+//      stages.
+//  26. Guardian death attestation. New GuardianAction::AttestDeath { remaining_seconds }
+//      goes through the usual proposal → quorum → delay flow. Executing it awakens the ghost
+//      (if it isn't already) and makes execute_legacy callable after `remaining_seconds`,
+//      which may be 0 to waive. It is never shorter than MIN_ATTESTATION_SAFETY_SECONDS and
+//      never later than the time-based path would allow. A live pause is left in place, and
+//      a ghost not yet awakened cannot execute before it ends. The warning stage is skipped,
+//      as there is no silence to warn about; the final notice stage still applies. New
+//      `attested_at: i64` and `attested_execute_at: i64` on GhostAccount. Within that window
+//      the owner cancels with ping or cancel_awakening. Recovery wallets and ping delegates
+//      cannot cancel an attested awakening: the former approved it, and the latter are hot
+//      keys that may outlive the owner. No silence bounty is paid.
+//
+// CLIENT IMPACT (v1.11):
+//   - frontend: add_beneficiary/update_beneficiary accept `token_mint = None` with action 0.
+//   - bot.js: route `token_mint = None` slots to execute_sol_transfer (no ATAs needed;
+//     protocol fee is paid to PROTOCOL_FEE_WALLET directly in lamports).
+//   - bot.js: execute_legacy now takes the vault PDA, the snapshot PDA (init, caller pays
+//     rent) and system_program, plus the vault's canonical ATA for every beneficiary mint
+//     in remaining_accounts (ATAs can be created permissionlessly first if missing).
+//   - bot.js: every execute_* payout/sweep instruction takes the snapshot PDA after `vault`.
+//   - bot.js: call migrate_ghost (any payer) on executed ghosts still below v1.11 before
+//     their residual sweep.
+//   - frontend: surface GuardianActionQueued to the owner — a ping inside the delay vetoes.
+//     RemoveBeneficiary proposals carry the slot's recipient and token_mint as well as index.
+//   - frontend + bot.js: derive the ghost/vault PDAs from `original_owner`, not `owner`.
+//     Look up a wallet's ghosts with a memcmp on `owner` (offset 8) instead of deriving.
+//     Guardian instructions still take an `owner` account — pass original_owner there.
+//   - frontend: `ping` no longer requires the signer to be the owner — delegates may sign.
+//   - watcher: relay signed heartbeats as [Ed25519Program.createInstructionWithPublicKey,
+//     ping_with_signature] — the sig-verify instruction must come right before it.
+//   - frontend + bot.js: a wallet's ghosts are the legacy PDA plus ["ghost", owner, u16 LE]
+//     for each index (or the GhostIndexed events); derive the vault with the same suffix.
+//   - bot.js: after execute_legacy, crank tally_beneficiary_account for every
+//     BeneficiaryAccount of the ghost (getProgramAccounts, memcmp ghost at offset 8). Then
+//     pay them via execute_transfer/execute_burn with `beneficiary_account` set (pass the
+//     program ID for the embedded slots). Residual sweeps now take the snapshot PDA too.
+//   - bot.js: route vesting slots to claim_vested on a schedule (e.g. daily); it fails with
+//     NothingVested until the next unlock.
+//   - bot.js: skip contingent slots in the execute_transfer loop. Call
+//     execute_contingent_transfer once their deadline passes.
+//   - bot.js: also call execute_contingent_transfer as soon as BeneficiaryDisclaimed
+//     reports routed_to = 2. Re-read slot amounts after a pro-rata disclaimer.
+//   - bot.js: replace the per-mint execute_whole_vault_transfer loop over
+//     getVaultTokenAccounts with batched execute_whole_vault_sweep calls, one per token
+//     program. `recipient` is now writable (it receives the closed accounts' rent).
+//   - bot.js: after the final payout, close paid beneficiary accounts, then call
+//     finalize_ghost with every still-open vault token account (getVaultTokenAccounts) as
+//     [vault ATA, token program] pairs. The rent recipient's $GHOST ATA must exist. Drop
+//     the ghost from the watch list on GhostFinalized.
+//   - frontend + bot.js: pass the config PDA ["config"] to initialize_ghost /
+//     initialize_indexed_ghost and every payout or sweep that takes a fee. Derive the fee
+//     ATAs from config.fee_wallet, not the hard-coded wallet. Deploy: call
+//     initialize_protocol_config right after the upgrade — fee-taking instructions fail
+//     until it exists.
+//   - frontend: offer initialize_sol_ghost as the no-token onboarding path. For ghosts with
+//     sol_staked, pass the program ID for the stake accounts of check_silence /
+//     abandon_ghost / finalize_ghost. bot.js: read bounty_paid as lamports for those ghosts.
+//   - frontend: abandon_ghost now takes the vault PDA and system_program. Pass every vault
+//     token account (getVaultTokenAccounts) as [vault ATA, mint, owner ATA, token program]
+//     groups. Remove beneficiary accounts before abandoning. Hide abandon for ghosts whose
+//     owner differs from original_owner.
+//   - frontend: pass the config PDA to abandon_ghost. Preview the burn from
+//     config.abandon_trial_seconds / abandon_burn_ramp_seconds and registered_at.
+//   - frontend: pause_ghost takes an `until` timestamp and the config PDA. Show paused_until.
+//     bot.js: a ghost is watchable again once paused_until has passed. Measure silence from
+//     max(last_heartbeat, paused_until), and optionally crank expire_pause.
+//   - bot.js: for ghosts with warning_bps / final_notice_bps set, crank issue_warning before
+//     check_silence and issue_final_notice before execute_legacy (same accounts as
+//     check_silence). GhostWarned has no bounty_paid; the check_silence bounty covers it.
+//   - frontend + guardians: subscribe to GhostWarned / GhostFinalNotice.
+//   - frontend: offer AttestDeath in the guardian proposal UI. Notify the owner on
+//     DeathAttested. bot.js: after DeathAttested, execute_legacy is callable from
+//     `execute_after` instead of awakened_at + grace_period_seconds. Hide cancel_awakening
+//     from recovery wallets while the awakening is attested.
+//
 // GHOST Protocol v1.10 — 2026-04-28 — SECURITY HARDENING
 // Changes from v1.9 (NO GhostAccount struct changes — same byte layout, no migration):
 //   1. CRITICAL FIX: ExecuteTransfer + ExecuteWholeVaultTransfer recipient_token_account
@@ -87,7 +353,7 @@
 //   2. Increase GHOST_ACCOUNT_SPACE by the byte size of the new field.
 //   3. Add a new SCHEMA_VERSION_V19: u8 = 19 constant below.
 //   4. Update CURRENT_SCHEMA_VERSION to point to the new constant.
-//   5. In initialize_ghost: set new_field = default_value; schema_version = CURRENT_SCHEMA_VERSION;
+//   5. In GhostAccount::initialize: set new_field = default_value; schema_version = CURRENT_SCHEMA_VERSION;
 //   6. In migrate_ghost: add realloc to new GHOST_ACCOUNT_SPACE, set new_field = default_value,
 //      set schema_version = CURRENT_SCHEMA_VERSION.
 //   7. In the frontend: bump MIN_SUPPORTED_VERSION and gate new UI features with isVersionSufficient().
@@ -105,22 +371,42 @@
 //   - Existing core features (ping, heartbeat, beneficiaries, vault) always work regardless of version
 // ═══════════════════════════════════════════════════════════════════════
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{self, Burn, TokenAccount, TokenInterface, TransferChecked, Mint};
+use anchor_lang::solana_program::{ed25519_program, sysvar::instructions as ix_sysvar};
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::token_interface::{self, Burn, CloseAccount, TokenAccount, TokenInterface, TransferChecked, Mint};
 
 declare_id!("3Es13GXc4qwttE6uSgAAfi1zvBD3qzLkZpY21KfT3sZ3");
 
 pub const GHOST_SEED: &[u8] = b"ghost";
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const MIN_STAKE: u64 = 10_000 * 1_000_000;
+pub const MIN_SOL_DEPOSIT_LAMPORTS: u64 = 100_000_000; // 0.1 SOL — initialize_sol_ghost
 pub const MIN_INTERVAL: i64 = 0;                 // 0 = instant expiry allowed (for testing)
 pub const MIN_GRACE_PERIOD: i64 = 0;             // 0 = instant execution allowed (was 24h)
 pub const MAX_BENEFICIARIES: usize = 10;
 pub const MAX_RECOVERY_WALLETS: usize = 3;
+pub const MAX_PING_DELEGATES: usize = 3;
 pub const SILENCE_BOUNTY_BPS: u64 = 500;
+pub const FINAL_NOTICE_BOUNTY_BPS: u64 = 100;    // v1.11: carved out of SILENCE_BOUNTY_BPS when the stage is enabled
 pub const BURN_ON_ABANDON_BPS: u64 = 5_000;
 pub const EXECUTION_FEE_BPS: u64 = 50;           // 0.5% fee on executed asset transfers
 pub const REGISTRATION_FEE_LAMPORTS: u64 = 20_000_000; // 0.02 SOL bot operations fee
 
+// ── Protocol config ──────────────────────────────────────────────────
+// v1.11: MIN_STAKE, MIN_SOL_DEPOSIT_LAMPORTS, EXECUTION_FEE_BPS, REGISTRATION_FEE_LAMPORTS and the two wallets below
+// are only the values initialize_protocol_config writes. Instructions read ProtocolConfig.
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const MAX_EXECUTION_FEE_BPS: u16 = 100;                  // hard cap: 1%
+pub const MAX_REGISTRATION_FEE_LAMPORTS: u64 = 1_000_000_000; // hard cap: 1 SOL
+pub const MAX_MIN_STAKE: u64 = 100 * MIN_STAKE;              // hard cap: 1M tokens
+pub const MAX_MIN_SOL_DEPOSIT_LAMPORTS: u64 = 10_000_000_000; // hard cap: 10 SOL
+pub const ABANDON_TRIAL_SECONDS: i64 = 7 * 86_400;          // full stake back in the first week
+pub const ABANDON_BURN_RAMP_SECONDS: i64 = 30 * 86_400;     // then 0 → BURN_ON_ABANDON_BPS over 30 days
+pub const MAX_ABANDON_TRIAL_SECONDS: i64 = 90 * 86_400;
+pub const MAX_PAUSE_SECONDS: i64 = 90 * 86_400;              // longest vacation, initially
+pub const MAX_PAUSE_SECONDS_CAP: i64 = 365 * 86_400;         // hard cap for update_max_pause
+pub const PROTOCOL_CONFIG_SPACE: usize = 8 + 32 + 2 + 8 + 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8;
+
 // ── Fee wallet addresses ─────────────────────────────────────────────
 // PROTOCOL_FEE_WALLET: receives 0.5% of executed token transfers
 // BOT_OPS_WALLET: receives 0.02 SOL registration fee for executor bot funding
@@ -133,7 +419,8 @@ pub const BOT_OPS_WALLET: Pubkey = Pubkey::new_from_array([181, 207, 57, 180, 20
 // update GHOST_ACCOUNT_SPACE, and follow the UPGRADE GUIDE at the top of this file.
 pub const SCHEMA_VERSION_V17: u8 = 17;           // legacy accounts — schema_version field did not exist yet
 pub const SCHEMA_VERSION_V18: u8 = 18;           // v1.8: schema_version added as last struct field
-pub const CURRENT_SCHEMA_VERSION: u8 = SCHEMA_VERSION_V18; // always points to latest — update on each upgrade
+pub const SCHEMA_VERSION_V111: u8 = 111;         // v1.11: see header items 1–26
+pub const CURRENT_SCHEMA_VERSION: u8 = SCHEMA_VERSION_V111; // always points to latest — update on each upgrade
 
 // ── Account space ────────────────────────────────────────────────────────
 // GHOST_ACCOUNT_SPACE must equal the exact Borsh-serialized byte size of GhostAccount
@@ -141,83 +428,534 @@ pub const CURRENT_SCHEMA_VERSION: u8 = SCHEMA_VERSION_V18; // always points to l
 // When adding a new field: increase this by the field's byte size.
 //   v1.7 = 1220 bytes (schema_version was a raw trailing byte, not in struct)
 //   v1.8 = 1221 bytes (schema_version: u8 added as proper last struct field)
-pub const GHOST_ACCOUNT_SPACE: usize = 1221;
+//   v1.11 = 2816 bytes (+700: beneficiary_ext [BeneficiaryExt; 10], 70 bytes each;
+//                       +33: residual_recipient Option<Pubkey>;
+//                       +9: guardian_threshold u8, proposal_count u64;
+//                       +17: guardian_delay_seconds i64, owner_ping_nonce u64, queued_guardian_actions u8;
+//                       +40: original_owner Pubkey, pending_owner_since i64;
+//                       +120: ping_delegates [PingDelegate; 3];
+//                       +8: heartbeat_nonce u64;
+//                       +3: ghost_index u16, indexed bool;
+//                       +8: beneficiary_accounts u32, next_beneficiary_id u32;
+//                       +1: disclaim_policy u8;
+//                       +33: rent_recipient Option<Pubkey>;
+//                       +2: execution_fee_bps u16;
+//                       +9: sol_staked bool, sol_deposit u64;
+//                       +8: paused_until i64;
+//                       +20: warning_bps u16, final_notice_bps u16, warned_at i64, final_notice_at i64;
+//                       +16: attested_at i64, attested_execute_at i64;
+//                       +560: account_mints [AccountMint; 16], 35 bytes each;
+//                       +8: stage_bounty_base u64)
+pub const GHOST_ACCOUNT_SPACE: usize = 2816;
+pub const MAX_SHARE_BPS: u64 = 10_000;
+
+// ── Execution snapshot ───────────────────────────────────────────────────
+// ExecutionSnapshot is a separate PDA, so it can grow without touching GhostAccount.
+pub const SNAPSHOT_SEED: &[u8] = b"snapshot";
+pub const MAX_SNAPSHOT_MINTS: usize = 16;
+pub const MINT_SNAPSHOT_SIZE: usize = 33 + 8 + 8 + 8 + 8;  // mint, balance, committed, paid, account_outstanding
+pub const MAX_SWEPT_MINTS: usize = 32;
+pub const SWEPT_MINT_SIZE: usize = 32 + 8 + 1;            // mint, amount, closed
+pub const EXECUTION_SNAPSHOT_SPACE: usize = 8 + 32 + 8 + 1 + 4 + 4 + 4 + MAX_SNAPSHOT_MINTS * MINT_SNAPSHOT_SIZE + 4 + MAX_SWEPT_MINTS * SWEPT_MINT_SIZE;
+
+// ── Beneficiary accounts ─────────────────────────────────────────────────
+pub const BENEFICIARY_SEED: &[u8] = b"beneficiary";
+pub const BENEFICIARY_SIZE: usize = 32 + 8 + 33 + 1 + 1;
+pub const BENEFICIARY_EXT_SIZE: usize = 2 + 8 + 8 + 2 + 8 + 33 + 8 + 1;
+pub const BENEFICIARY_ACCOUNT_SPACE: usize = 8 + 32 + 4 + BENEFICIARY_SIZE + BENEFICIARY_EXT_SIZE + 1 + 1;
+
+// ── Disclaimers ──────────────────────────────────────────────────────────
+pub const DISCLAIM_POLICY_ESTATE: u8 = 0;        // lapses to whole_vault_recipient / residual_recipient
+pub const DISCLAIM_POLICY_PRO_RATA: u8 = 1;      // shared across the remaining slots of the mint
+pub const DISCLAIM_ROUTED_CONTINGENT: u8 = 2;    // BeneficiaryDisclaimed only — the slot's contingent recipient
+
+// ── Guardian proposals ───────────────────────────────────────────────────
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const PROPOSAL_TTL_SECONDS: i64 = 7 * 24 * 60 * 60;
+pub const MAX_GUARDIAN_DELAY_SECONDS: i64 = 30 * 24 * 60 * 60;
+pub const OWNERSHIP_TRANSFER_COOLDOWN_SECONDS: i64 = 3 * 24 * 60 * 60;
+pub const MIN_ATTESTATION_SAFETY_SECONDS: i64 = 2 * 24 * 60 * 60; // owner's last chance after AttestDeath
+// ── Relayed heartbeats ───────────────────────────────────────────────────
+pub const SIGNED_PING_MAX_AGE_SECONDS: i64 = 24 * 60 * 60;
+pub const SIGNED_PING_MAX_SKEW_SECONDS: i64 = 5 * 60;
+pub const GUARDIAN_ACTION_SIZE: usize = 1 + 32 + 8 + 32;  // largest variant: RecoveryWithdraw
+pub const GUARDIAN_PROPOSAL_SPACE: usize = 8 + 32 + 8 + 32 + GUARDIAN_ACTION_SIZE + 99 + 1 + 8 + 8 + 1 + 1 + 8 + 9 + 1;
+
+// v1.11: checks that the instruction right before the current one is an Ed25519 sig-verify
+// of exactly `message` by `signer`, with signature, key and message all inline in that
+// instruction's data. The precompile has already rejected the transaction if the signature
+// itself is bad — this only proves *what* it verified.
+fn verify_ed25519_signature(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = ix_sysvar::load_current_index_checked(instructions)?;
+    require!(current > 0, GhostError::InvalidSignedHeartbeat);
+    let ix = ix_sysvar::load_instruction_at_checked((current - 1) as usize, instructions)?;
+    require!(ix.program_id == ed25519_program::ID && ix.accounts.is_empty(), GhostError::InvalidSignedHeartbeat);
+    let data = &ix.data;
+    // [num_signatures u8, padding u8, then 7 x u16 offsets]
+    require!(data.len() >= 16 && data[0] == 1, GhostError::InvalidSignedHeartbeat);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_ix = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_ix = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix = read_u16(14);
+    require!(signature_ix == u16::MAX && public_key_ix == u16::MAX && message_ix == u16::MAX, GhostError::InvalidSignedHeartbeat);
+    let public_key = data.get(public_key_offset..public_key_offset + 32).ok_or(GhostError::InvalidSignedHeartbeat)?;
+    let signed = data.get(message_offset..message_offset + message_size).ok_or(GhostError::InvalidSignedHeartbeat)?;
+    require!(public_key == signer.as_ref() && signed == message, GhostError::InvalidSignedHeartbeat);
+    Ok(())
+}
+
+// Registration fee → bot operations wallet, charged on every registration path.
+fn pay_registration_fee<'info>(config: &ProtocolConfig, signer: &Signer<'info>, bot_ops_wallet: &UncheckedAccount<'info>) -> Result<()> {
+    let ix = anchor_lang::solana_program::system_instruction::transfer(
+        &signer.key(),
+        &config.bot_ops_wallet,
+        config.registration_fee_lamports,
+    );
+    anchor_lang::solana_program::program::invoke(
+        &ix,
+        &[
+            signer.to_account_info(),
+            bot_ops_wallet.to_account_info(),
+        ],
+    )?;
+    Ok(())
+}
+
+// Registration fee + $GHOST stake, shared by initialize_ghost and initialize_indexed_ghost.
+#[allow(clippy::too_many_arguments)]
+fn collect_registration<'info>(
+    config: &ProtocolConfig,
+    signer: &Signer<'info>,
+    bot_ops_wallet: &UncheckedAccount<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    signer_token_account: &InterfaceAccount<'info, TokenAccount>,
+    ghost_stake_vault: &InterfaceAccount<'info, TokenAccount>,
+    ghost_mint: &InterfaceAccount<'info, Mint>,
+    stake_amount: u64,
+) -> Result<()> {
+    pay_registration_fee(config, signer, bot_ops_wallet)?;
+
+    // ── Stake $GHOST transfer ───────────────────────────────────────────
+    let cpi_ctx = CpiContext::new(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: signer_token_account.to_account_info(),
+            to: ghost_stake_vault.to_account_info(),
+            authority: signer.to_account_info(),
+            mint: ghost_mint.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(cpi_ctx, stake_amount, ghost_mint.decimals)?;
+    Ok(())
+}
 
 fn is_recovery_wallet(wallets: &[Option<Pubkey>; 3], key: Pubkey) -> bool {
     wallets.iter().any(|slot| slot.map_or(false, |w| w == key))
 }
 
+// v1.11: slot index of a recovery wallet — proposal approvals are a bitmap over these.
+fn recovery_wallet_index(wallets: &[Option<Pubkey>; 3], key: Pubkey) -> Option<usize> {
+    wallets.iter().position(|slot| *slot == Some(key))
+}
+
+// v1.11: lamports held by the vault PDA above its rent-exempt reserve. The vault is a
+// data-less system account, so the reserve is Rent::minimum_balance(0) and must never
+// be transferred out (the runtime rejects leaving a system account below rent).
+fn vault_sol_available(vault: &AccountInfo) -> Result<u64> {
+    let reserve = Rent::get()?.minimum_balance(0);
+    Ok(vault.lamports().saturating_sub(reserve))
+}
+
+// v1.11: deserialize a caller-supplied account as the vault PDA's canonical ATA.
+// Returns None for anything that isn't a Token / Token-2022 account owned by `vault` at
+// the associated address for its mint, so callers can scan remaining_accounts without
+// trusting their order or contents — and an empty decoy account held by the vault can't
+// stand in for the real balance.
+fn read_vault_token_account(info: &AccountInfo, vault: Pubkey) -> Option<TokenAccount> {
+    if info.owner != &anchor_spl::token::ID && info.owner != &anchor_spl::token_2022::ID {
+        return None;
+    }
+    let data = info.try_borrow_data().ok()?;
+    let account = TokenAccount::try_deserialize(&mut &data[..]).ok()?;
+    if account.owner != vault { return None; }
+    if info.key() != get_associated_token_address_with_program_id(&vault, &account.mint, info.owner) { return None; }
+    Some(account)
+}
+
+// v1.11: deserialize a caller-supplied token account, checking its program, authority and mint.
+fn read_token_account(info: &AccountInfo, token_program: &Pubkey, authority: Pubkey, mint: Pubkey) -> Result<TokenAccount> {
+    require!(info.owner == token_program, GhostError::InvalidSweepAccounts);
+    let data = info.try_borrow_data()?;
+    let account = TokenAccount::try_deserialize(&mut &data[..])?;
+    require!(account.owner == authority && account.mint == mint, GhostError::InvalidSweepAccounts);
+    Ok(account)
+}
+
+// v1.11: vault balance for a beneficiary mint. None = native SOL (vault lamports above
+// rent); Some(mint) must be backed by a vault token account in `remaining`.
+fn vault_balance_for_mint(vault: &AccountInfo, remaining: &[AccountInfo], mint: Option<Pubkey>) -> Result<u64> {
+    match mint {
+        None => vault_sol_available(vault),
+        Some(mint) => remaining
+            .iter()
+            .filter_map(|info| read_vault_token_account(info, vault.key()))
+            .find(|account| account.mint == mint)
+            .map(|account| account.amount)
+            .ok_or_else(|| error!(GhostError::MissingVaultTokenAccount)),
+    }
+}
+
+// v1.11: one MintSnapshot per distinct mint among the unpaid slots, with the vault balance
+// read now (from the canonical ATA — see read_vault_token_account) and the slot amounts
+// committed against it. The snapshot is permanent, so nothing else may supply the balance.
+fn build_snapshot_mints(ghost: &GhostAccount, vault: &AccountInfo, remaining: &[AccountInfo]) -> Result<Vec<MintSnapshot>> {
+    let mut mints: Vec<MintSnapshot> = Vec::new();
+    for i in 0..ghost.beneficiary_count as usize {
+        let beneficiary = ghost.beneficiaries[i];
+        if beneficiary.executed { continue; }
+        let pos = match mints.iter().position(|m| m.mint == beneficiary.token_mint) {
+            Some(pos) => pos,
+            None => {
+                require!(mints.len() < MAX_SNAPSHOT_MINTS, GhostError::TooManySnapshotMints);
+                let balance = vault_balance_for_mint(vault, remaining, beneficiary.token_mint)?;
+                mints.push(MintSnapshot { mint: beneficiary.token_mint, balance, committed: 0, paid: 0, account_outstanding: 0 });
+                mints.len() - 1
+            }
+        };
+        mints[pos].committed = mints[pos].committed.checked_add(beneficiary.amount).ok_or(GhostError::Overflow)?;
+    }
+    Ok(mints)
+}
+
+// Protocol fee on `amount` at `fee_bps`, rounded down. Returns (to the recipient, to the fee wallet).
+fn split_fee(amount: u64, fee_bps: u64) -> (u64, u64) {
+    let fee = (amount as u128 * fee_bps as u128 / 10_000) as u64;
+    (amount - fee, fee)
+}
+
+// Pays `amount` out of a vault token account, signed by the vault PDA: the protocol fee to
+// `fee_token_account`, the rest to `to`. Returns (transfer_amount, fee_amount).
+#[allow(clippy::too_many_arguments)]
+fn transfer_with_fee<'info>(
+    token_program: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    fee_token_account: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    amount: u64,
+    fee_bps: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<(u64, u64)> {
+    let (transfer_amount, fee_amount) = split_fee(amount, fee_bps);
+    if transfer_amount > 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.clone(),
+            TransferChecked { from: from.clone(), to, authority: vault.clone(), mint: mint.clone() },
+            signer_seeds,
+        );
+        token_interface::transfer_checked(cpi_ctx, transfer_amount, decimals)?;
+    }
+    if fee_amount > 0 {
+        let fee_ctx = CpiContext::new_with_signer(
+            token_program,
+            TransferChecked { from, to: fee_token_account, authority: vault, mint },
+            signer_seeds,
+        );
+        token_interface::transfer_checked(fee_ctx, fee_amount, decimals)?;
+    }
+    Ok((transfer_amount, fee_amount))
+}
+
+// Lamport counterpart of transfer_with_fee: system transfers out of the vault PDA.
+fn transfer_lamports_with_fee<'info>(
+    system_program: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    fee_wallet: AccountInfo<'info>,
+    amount: u64,
+    fee_bps: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<(u64, u64)> {
+    let (transfer_amount, fee_amount) = split_fee(amount, fee_bps);
+    if transfer_amount > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer { from: vault.clone(), to },
+                signer_seeds,
+            ),
+            transfer_amount,
+        )?;
+    }
+    if fee_amount > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                system_program,
+                anchor_lang::system_program::Transfer { from: vault, to: fee_wallet },
+                signer_seeds,
+            ),
+            fee_amount,
+        )?;
+    }
+    Ok((transfer_amount, fee_amount))
+}
+
+// v1.11: vault balance still owed to unpaid slots of `mint` under the snapshot. Whole-vault
+// sweeps may only move what is above this, so they can never front-run a beneficiary.
+// Beneficiary accounts are reserved as one lump (entitlement of the sum), which can only
+// round up relative to paying them one by one.
+fn reserved_for_mint(ghost: &GhostAccount, snapshot: &ExecutionSnapshot, mint: Option<Pubkey>) -> Result<u64> {
+    snapshot.require_tallied()?;
+    let Some(entry) = snapshot.entry(mint) else { return Ok(0) };
+    let embedded: u64 = (0..ghost.beneficiary_count as usize)
+        .filter(|&i| !ghost.beneficiaries[i].executed && ghost.beneficiaries[i].token_mint == mint)
+        .map(|i| entry.entitlement(ghost.beneficiaries[i].amount).saturating_sub(ghost.slot_claimed(i)))
+        .sum();
+    Ok(embedded + entry.entitlement(entry.account_outstanding))
+}
+
+// v1.11: nothing is owed to any beneficiary — embedded slots and beneficiary accounts alike.
+fn beneficiaries_settled(ghost: &GhostAccount, snapshot: &ExecutionSnapshot) -> bool {
+    ghost.all_slots_executed() && snapshot.pending_tallies == 0 && snapshot.accounts_unpaid == 0
+}
+
+// v1.11: pays the caller `bounty_bps` of the awakening's stage_bounty_base — lamports out of
+// the ghost PDA for SOL-deposit ghosts, $GHOST out of the stake vault otherwise.
+fn pay_stage_bounty(accounts: &mut CheckSilence, bounty_bps: u64) -> Result<u64> {
+    let base = accounts.ghost.stage_bounty_base;
+    if accounts.ghost.is_sol_staked() {
+        let sol_deposit = accounts.ghost.sol_deposit;
+        let bounty = base.checked_mul(bounty_bps).unwrap().checked_div(10_000).unwrap().min(sol_deposit);
+        **accounts.ghost.to_account_info().try_borrow_mut_lamports()? -= bounty;
+        **accounts.caller.to_account_info().try_borrow_mut_lamports()? += bounty;
+        accounts.ghost.sol_deposit = sol_deposit - bounty;
+        return Ok(bounty);
+    }
+    let (Some(ghost_mint), Some(ghost_stake_vault), Some(caller_token_account), Some(token_program)) = (
+        accounts.ghost_mint.as_ref(),
+        accounts.ghost_stake_vault.as_ref(),
+        accounts.caller_token_account.as_ref(),
+        accounts.token_program.as_ref(),
+    ) else {
+        return err!(GhostError::MissingStakeAccounts);
+    };
+    let staked_ghost = accounts.ghost.staked_ghost;
+    let bounty = base.checked_mul(bounty_bps).unwrap().checked_div(10_000).unwrap().min(staked_ghost);
+    let seed_owner = *accounts.ghost.seed_owner();
+    let index_seed = accounts.ghost.index_seed();
+    let seeds = &[GHOST_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[accounts.ghost.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: ghost_stake_vault.to_account_info(),
+            to: caller_token_account.to_account_info(),
+            authority: accounts.ghost.to_account_info(),
+            mint: ghost_mint.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, bounty, ghost_mint.decimals)?;
+    accounts.ghost.staked_ghost = staked_ghost.checked_sub(bounty).unwrap();
+    Ok(bounty)
+}
+
+// v1.11: sum of share_bps already allocated to `mint`, ignoring slot `skip`.
+fn share_bps_for_mint(ghost: &GhostAccount, mint: Option<Pubkey>, skip: usize) -> u64 {
+    (0..ghost.beneficiary_count as usize)
+        .filter(|&i| i != skip && ghost.beneficiaries[i].token_mint == mint)
+        .map(|i| ghost.beneficiary_ext[i].share_bps as u64)
+        .sum()
+}
+
 #[program]
 pub mod ghost_protocol {
     use super::*;
 
+    // v1.11: one-time creation of the ProtocolConfig singleton, by the program upgrade
+    // authority, with the pre-v1.11 constants as initial values.
+    pub fn initialize_protocol_config(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.signer.key();
+        config.execution_fee_bps = EXECUTION_FEE_BPS as u16;
+        config.registration_fee_lamports = REGISTRATION_FEE_LAMPORTS;
+        config.min_stake = MIN_STAKE;
+        config.fee_wallet = PROTOCOL_FEE_WALLET;
+        config.bot_ops_wallet = BOT_OPS_WALLET;
+        config.bump = ctx.bumps.config;
+        config.min_sol_deposit = MIN_SOL_DEPOSIT_LAMPORTS;
+        config.abandon_trial_seconds = ABANDON_TRIAL_SECONDS;
+        config.abandon_burn_ramp_seconds = ABANDON_BURN_RAMP_SECONDS;
+        config.max_pause_seconds = MAX_PAUSE_SECONDS;
+        emit!(ProtocolConfigInitialized { admin: config.admin });
+        msg!("Protocol config initialized, admin {}", config.admin);
+        Ok(())
+    }
+
+    pub fn update_protocol_fees(ctx: Context<UpdateProtocolConfig>, execution_fee_bps: u16, registration_fee_lamports: u64, min_stake: u64, min_sol_deposit: u64) -> Result<()> {
+        require!(execution_fee_bps <= MAX_EXECUTION_FEE_BPS, GhostError::FeeAboveCap);
+        require!(registration_fee_lamports <= MAX_REGISTRATION_FEE_LAMPORTS, GhostError::FeeAboveCap);
+        require!(min_stake <= MAX_MIN_STAKE, GhostError::FeeAboveCap);
+        require!(min_sol_deposit <= MAX_MIN_SOL_DEPOSIT_LAMPORTS, GhostError::FeeAboveCap);
+        let config = &mut ctx.accounts.config;
+        let previous_execution_fee_bps = config.execution_fee_bps;
+        config.execution_fee_bps = execution_fee_bps;
+        config.registration_fee_lamports = registration_fee_lamports;
+        config.min_stake = min_stake;
+        config.min_sol_deposit = min_sol_deposit;
+        emit!(ProtocolFeesUpdated { previous_execution_fee_bps, execution_fee_bps, registration_fee_lamports, min_stake, min_sol_deposit });
+        msg!("Protocol fees updated: {} bps, {} lamports registration, {} min stake", execution_fee_bps, registration_fee_lamports, min_stake);
+        Ok(())
+    }
+
+    // A ramp of 0 burns the full BURN_ON_ABANDON_BPS as soon as the trial ends.
+    pub fn update_abandon_schedule(ctx: Context<UpdateProtocolConfig>, trial_seconds: i64, burn_ramp_seconds: i64) -> Result<()> {
+        require!((0..=MAX_ABANDON_TRIAL_SECONDS).contains(&trial_seconds), GhostError::InvalidAbandonSchedule);
+        require!(burn_ramp_seconds >= 0, GhostError::InvalidAbandonSchedule);
+        let config = &mut ctx.accounts.config;
+        config.abandon_trial_seconds = trial_seconds;
+        config.abandon_burn_ramp_seconds = burn_ramp_seconds;
+        emit!(AbandonScheduleUpdated { trial_seconds, burn_ramp_seconds });
+        msg!("Abandon schedule updated: {}s trial, {}s burn ramp", trial_seconds, burn_ramp_seconds);
+        Ok(())
+    }
+
+    pub fn update_max_pause(ctx: Context<UpdateProtocolConfig>, max_pause_seconds: i64) -> Result<()> {
+        require!(max_pause_seconds > 0 && max_pause_seconds <= MAX_PAUSE_SECONDS_CAP, GhostError::InvalidPauseDuration);
+        ctx.accounts.config.max_pause_seconds = max_pause_seconds;
+        emit!(MaxPauseUpdated { max_pause_seconds });
+        msg!("Max pause updated: {}s", max_pause_seconds);
+        Ok(())
+    }
+
+    pub fn update_protocol_wallets(ctx: Context<UpdateProtocolConfig>, fee_wallet: Pubkey, bot_ops_wallet: Pubkey) -> Result<()> {
+        require!(fee_wallet != Pubkey::default() && bot_ops_wallet != Pubkey::default(), GhostError::InvalidProtocolWallet);
+        let config = &mut ctx.accounts.config;
+        config.fee_wallet = fee_wallet;
+        config.bot_ops_wallet = bot_ops_wallet;
+        emit!(ProtocolWalletsUpdated { fee_wallet, bot_ops_wallet });
+        msg!("Protocol wallets updated");
+        Ok(())
+    }
+
+    // Both keys sign, so the admin role can't be handed to a key nobody controls.
+    pub fn set_protocol_admin(ctx: Context<SetProtocolAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let previous = config.admin;
+        config.admin = ctx.accounts.new_admin.key();
+        emit!(ProtocolAdminChanged { previous, admin: config.admin });
+        msg!("Protocol admin changed to {}", config.admin);
+        Ok(())
+    }
+
     pub fn initialize_ghost(
         ctx: Context<InitializeGhost>,
         interval_seconds: i64,
         grace_period_seconds: i64,
         stake_amount: u64,
     ) -> Result<()> {
-        require!(stake_amount >= MIN_STAKE, GhostError::InsufficientStake);
+        require!(stake_amount >= ctx.accounts.config.min_stake, GhostError::InsufficientStake);
         require!(interval_seconds >= MIN_INTERVAL, GhostError::IntervalTooShort);
         require!(grace_period_seconds >= MIN_GRACE_PERIOD, GhostError::GracePeriodTooShort);
 
-        let ghost = &mut ctx.accounts.ghost;
         let clock = Clock::get()?;
+        let ghost = &mut ctx.accounts.ghost;
+        ghost.initialize(ctx.accounts.signer.key(), interval_seconds, grace_period_seconds, stake_amount, ctx.bumps.ghost, ctx.bumps.vault, clock.unix_timestamp, ctx.accounts.config.execution_fee_bps);
 
-        ghost.owner = ctx.accounts.signer.key();
-        ghost.recovery_wallets = [None, None, None];
-        ghost.last_heartbeat = clock.unix_timestamp;
-        ghost.interval_seconds = interval_seconds;
-        ghost.grace_period_seconds = grace_period_seconds;
-        ghost.awakened = false;
-        ghost.awakened_at = None;
-        ghost.executed = false;
-        ghost.executed_at = None;
-        ghost.staked_ghost = stake_amount;
-        ghost.bump = ctx.bumps.ghost;
-        ghost.vault_bump = ctx.bumps.vault;
-        ghost.registered_at = clock.unix_timestamp;
-        ghost.ping_count = 0;
-        ghost.beneficiary_count = 0;
-        ghost.whole_vault_action = 0;
-        ghost.display_name = [0u8; 32];
-        ghost.image_uri = [0u8; 128];
-        ghost.whole_vault_recipient = None;
-        ghost.paused = false;
-        ghost.pending_owner = None;
-        for i in 0..10 {
-            ghost.beneficiaries[i] = Beneficiary::default();
-        }
-        // v1.8: set schema_version on new accounts so they never need migration.
-        // Future versions: update this line to use CURRENT_SCHEMA_VERSION (which you
-        // should update to point to the new SCHEMA_VERSION_VXX constant).
-        ghost.schema_version = CURRENT_SCHEMA_VERSION;
+        collect_registration(
+            &ctx.accounts.config,
+            &ctx.accounts.signer,
+            &ctx.accounts.bot_ops_wallet,
+            &ctx.accounts.token_program,
+            &ctx.accounts.signer_token_account,
+            &ctx.accounts.ghost_stake_vault,
+            &ctx.accounts.ghost_mint,
+            stake_amount,
+        )?;
 
-        // ── Registration fee: 0.02 SOL → bot operations wallet ──────────────
-        let ix = anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.signer.key(),
-            &BOT_OPS_WALLET,
-            REGISTRATION_FEE_LAMPORTS,
-        );
-        anchor_lang::solana_program::program::invoke(
-            &ix,
-            &[
-                ctx.accounts.signer.to_account_info(),
-                ctx.accounts.bot_ops_wallet.to_account_info(),
-            ],
+        emit!(GhostRegistered {
+            soul: ghost.owner,
+            interval: interval_seconds,
+            grace_period: grace_period_seconds,
+            recovery_wallets: [None, None, None],
+            staked: stake_amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Ghost initialized for {}", ghost.owner);
+        Ok(())
+    }
+
+    // v1.11: registration without $GHOST. The deposit sits in the ghost PDA above its rent —
+    // check_silence pays its bounty from it in lamports and abandon_ghost refunds it.
+    pub fn initialize_sol_ghost(ctx: Context<InitializeSolGhost>, interval_seconds: i64, grace_period_seconds: i64, deposit_lamports: u64) -> Result<()> {
+        require!(deposit_lamports >= ctx.accounts.config.min_sol_deposit, GhostError::InsufficientStake);
+        require!(interval_seconds >= MIN_INTERVAL, GhostError::IntervalTooShort);
+        require!(grace_period_seconds >= MIN_GRACE_PERIOD, GhostError::GracePeriodTooShort);
+
+        pay_registration_fee(&ctx.accounts.config, &ctx.accounts.signer, &ctx.accounts.bot_ops_wallet)?;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer { from: ctx.accounts.signer.to_account_info(), to: ctx.accounts.ghost.to_account_info() },
+            ),
+            deposit_lamports,
         )?;
 
-        // ── Stake $GHOST transfer ───────────────────────────────────────────
-        let cpi_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked {
-                from: ctx.accounts.signer_token_account.to_account_info(),
-                to: ctx.accounts.ghost_stake_vault.to_account_info(),
-                authority: ctx.accounts.signer.to_account_info(),
-                mint: ctx.accounts.ghost_mint.to_account_info(),
-            },
-        );
-        token_interface::transfer_checked(cpi_ctx, stake_amount, ctx.accounts.ghost_mint.decimals)?;
+        let clock = Clock::get()?;
+        let ghost = &mut ctx.accounts.ghost;
+        ghost.initialize(ctx.accounts.signer.key(), interval_seconds, grace_period_seconds, 0, ctx.bumps.ghost, ctx.bumps.vault, clock.unix_timestamp, ctx.accounts.config.execution_fee_bps);
+        ghost.sol_staked = true;
+        ghost.sol_deposit = deposit_lamports;
+
+        emit!(GhostRegistered {
+            soul: ghost.owner,
+            interval: interval_seconds,
+            grace_period: grace_period_seconds,
+            recovery_wallets: [None, None, None],
+            staked: 0,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(SolDepositLocked { soul: ghost.owner, amount: deposit_lamports });
+        msg!("Ghost initialized for {} with a {} lamport deposit", ghost.owner, deposit_lamports);
+        Ok(())
+    }
+
+    // v1.11: an additional, independent ghost for the same wallet at ["ghost", owner, index LE].
+    // The legacy index-less ghost is unaffected; any index may be used in any order.
+    pub fn initialize_indexed_ghost(
+        ctx: Context<InitializeIndexedGhost>,
+        ghost_index: u16,
+        interval_seconds: i64,
+        grace_period_seconds: i64,
+        stake_amount: u64,
+    ) -> Result<()> {
+        require!(stake_amount >= ctx.accounts.config.min_stake, GhostError::InsufficientStake);
+        require!(interval_seconds >= MIN_INTERVAL, GhostError::IntervalTooShort);
+        require!(grace_period_seconds >= MIN_GRACE_PERIOD, GhostError::GracePeriodTooShort);
+
+        let clock = Clock::get()?;
+        let ghost_key = ctx.accounts.ghost.key();
+        let ghost = &mut ctx.accounts.ghost;
+        ghost.initialize(ctx.accounts.signer.key(), interval_seconds, grace_period_seconds, stake_amount, ctx.bumps.ghost, ctx.bumps.vault, clock.unix_timestamp, ctx.accounts.config.execution_fee_bps);
+        ghost.ghost_index = ghost_index;
+        ghost.indexed = true;
+
+        collect_registration(
+            &ctx.accounts.config,
+            &ctx.accounts.signer,
+            &ctx.accounts.bot_ops_wallet,
+            &ctx.accounts.token_program,
+            &ctx.accounts.signer_token_account,
+            &ctx.accounts.ghost_stake_vault,
+            &ctx.accounts.ghost_mint,
+            stake_amount,
+        )?;
 
         emit!(GhostRegistered {
             soul: ghost.owner,
@@ -227,8 +965,9 @@ pub mod ghost_protocol {
             staked: stake_amount,
             timestamp: clock.unix_timestamp,
         });
+        emit!(GhostIndexed { soul: ghost.owner, ghost: ghost_key, ghost_index });
 
-        msg!("Ghost initialized for {}", ghost.owner);
+        msg!("Ghost #{} initialized for {}", ghost_index, ghost.owner);
         Ok(())
     }
 
@@ -236,6 +975,12 @@ pub mod ghost_protocol {
         let ghost = &mut ctx.accounts.ghost;
         require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
         let clock = Clock::get()?;
+        // v1.11: owner or a live ping delegate
+        let signer = ctx.accounts.signer.key();
+        let is_owner = ghost.owner == signer;
+        require!(is_owner || ghost.is_ping_delegate(signer, clock.unix_timestamp), GhostError::Unauthorized);
+        // A delegate is a hot key; it may not undo a guardian attestation
+        require!(ghost.may_cancel_awakening(is_owner), GhostError::Unauthorized);
         ghost.last_heartbeat = clock.unix_timestamp;
         ghost.ping_count += 1;
         if ghost.awakened {
@@ -245,20 +990,101 @@ pub mod ghost_protocol {
         } else {
             msg!("Heartbeat #{} recorded", ghost.ping_count);
         }
+        if is_owner {
+            // v1.11: an owner ping vetoes every queued guardian action
+            let vetoed = ghost.record_owner_ping();
+            if vetoed > 0 {
+                emit!(GuardianActionVetoed { soul: ghost.owner, proposal_id: None, count: vetoed, timestamp: clock.unix_timestamp });
+            }
+        } else {
+            msg!("Pinged by delegate {}", signer);
+        }
         emit!(HeartbeatReceived { soul: ghost.owner, timestamp: clock.unix_timestamp, ping_number: ghost.ping_count });
         Ok(())
     }
 
+    // v1.11: owner-signed heartbeat submitted by any relayer (who only pays the fee).
+    pub fn ping_with_signature(ctx: Context<PingWithSignature>, nonce: u64, timestamp: i64) -> Result<()> {
+        let ghost_key = ctx.accounts.ghost.key();
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!(nonce == ghost.heartbeat_nonce, GhostError::InvalidSignedHeartbeat);
+        let clock = Clock::get()?;
+        require!(timestamp <= clock.unix_timestamp + SIGNED_PING_MAX_SKEW_SECONDS, GhostError::InvalidSignedHeartbeat);
+        require!(timestamp >= clock.unix_timestamp - SIGNED_PING_MAX_AGE_SECONDS, GhostError::SignedHeartbeatExpired);
+        // A signature from before the awakening (or the last heartbeat) proves nothing new —
+        // otherwise a relayer could hold one back and replay it to undo a legitimate awakening
+        require!(timestamp > ghost.awakened_at.unwrap_or(ghost.last_heartbeat), GhostError::SignedHeartbeatExpired);
+
+        let mut message = [0u8; 48];
+        message[..32].copy_from_slice(ghost_key.as_ref());
+        message[32..40].copy_from_slice(&nonce.to_le_bytes());
+        message[40..].copy_from_slice(&timestamp.to_le_bytes());
+        verify_ed25519_signature(&ctx.accounts.instructions.to_account_info(), &ghost.owner, &message)?;
+
+        ghost.heartbeat_nonce += 1;
+        // Proof of life is as of the signature, not the relay; never moves backwards
+        ghost.last_heartbeat = ghost.last_heartbeat.max(timestamp.min(clock.unix_timestamp));
+        ghost.ping_count += 1;
+        if ghost.awakened {
+            ghost.awakened = false;
+            ghost.awakened_at = None;
+            msg!("Signed ping received - awakening cancelled");
+        } else {
+            msg!("Signed heartbeat #{} relayed by {}", ghost.ping_count, ctx.accounts.relayer.key());
+        }
+        let vetoed = ghost.record_owner_ping();
+        if vetoed > 0 {
+            emit!(GuardianActionVetoed { soul: ghost.owner, proposal_id: None, count: vetoed, timestamp: clock.unix_timestamp });
+        }
+        emit!(HeartbeatReceived { soul: ghost.owner, timestamp: ghost.last_heartbeat, ping_number: ghost.ping_count });
+        Ok(())
+    }
+
+    // v1.11: register (or re-expire) a ping-only key. Expired slots are reused.
+    pub fn add_ping_delegate(ctx: Context<UpdateSettings>, delegate: Pubkey, expires_at: i64) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!(delegate != ghost.owner && delegate != Pubkey::default(), GhostError::InvalidPingDelegate);
+        let clock = Clock::get()?;
+        require!(expires_at > clock.unix_timestamp, GhostError::InvalidPingDelegate);
+        let slot = ghost.ping_delegates.iter().position(|d| d.key == delegate)
+            .or_else(|| ghost.ping_delegates.iter().position(|d| !d.is_live(clock.unix_timestamp)))
+            .ok_or(GhostError::TooManyPingDelegates)?;
+        ghost.ping_delegates[slot] = PingDelegate { key: delegate, expires_at };
+        emit!(PingDelegateAdded { soul: ghost.owner, delegate, expires_at });
+        msg!("Ping delegate {} added until {}", delegate, expires_at);
+        Ok(())
+    }
+
+    pub fn remove_ping_delegate(ctx: Context<UpdateSettings>, delegate: Pubkey) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        let slot = ghost.ping_delegates.iter().position(|d| d.key == delegate && delegate != Pubkey::default())
+            .ok_or(GhostError::InvalidPingDelegate)?;
+        ghost.ping_delegates[slot] = PingDelegate::default();
+        emit!(PingDelegateRemoved { soul: ghost.owner, delegate });
+        msg!("Ping delegate {} removed", delegate);
+        Ok(())
+    }
+
     pub fn add_beneficiary(ctx: Context<ManageBeneficiaries>, recipient: Pubkey, amount: u64, token_mint: Option<Pubkey>, action: u8) -> Result<()> {
         let ghost = &mut ctx.accounts.ghost;
         require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
-        require!(!ghost.paused, GhostError::GhostPausedError);
+        let clock = Clock::get()?;
+        require!(!ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
         require!((ghost.beneficiary_count as usize) < MAX_BENEFICIARIES, GhostError::TooManyBeneficiaries);
         // v1.10: validate inputs to prevent unclaimable slots
-        require!(token_mint.is_some(), GhostError::WrongMint);
+        // v1.11: token_mint = None is a native SOL slot — only Transfer is executable
         require!(action <= 1, GhostError::InvalidBeneficiaryAction);
+        require!(token_mint.is_some() || action == 0, GhostError::InvalidBeneficiaryAction);
+        // v1.11: every mint must still fit in the execution snapshot
+        require!(!ghost.is_v111() || ghost.distinct_mints_with(token_mint, None, None) <= MAX_SNAPSHOT_MINTS, GhostError::TooManySnapshotMints);
         let idx = ghost.beneficiary_count as usize;
         ghost.beneficiaries[idx] = Beneficiary { recipient, amount, token_mint, action, executed: false };
+        ghost.beneficiary_ext[idx] = BeneficiaryExt::default();
         ghost.beneficiary_count += 1;
         emit!(BeneficiaryAdded { soul: ghost.owner, recipient, amount, action });
         msg!("Beneficiary added: {} receives {}", recipient, amount);
@@ -268,10 +1094,7 @@ pub mod ghost_protocol {
     pub fn remove_beneficiary(ctx: Context<ManageBeneficiaries>, index: u8) -> Result<()> {
         let ghost = &mut ctx.accounts.ghost;
         require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
-        require!((index as usize) < ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
-        let count = ghost.beneficiary_count as usize;
-        for i in (index as usize)..(count - 1) { ghost.beneficiaries[i] = ghost.beneficiaries[i + 1]; }
-        ghost.beneficiary_count -= 1;
+        ghost.remove_beneficiary_at(index)?;
         msg!("Beneficiary at index {} removed", index);
         Ok(())
     }
@@ -279,11 +1102,27 @@ pub mod ghost_protocol {
     pub fn update_beneficiary(ctx: Context<ManageBeneficiaries>, index: u8, recipient: Pubkey, amount: u64, token_mint: Option<Pubkey>, action: u8) -> Result<()> {
         let ghost = &mut ctx.accounts.ghost;
         require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
-        require!(!ghost.paused, GhostError::GhostPausedError);
+        let clock = Clock::get()?;
+        require!(!ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
         // v1.10: validate inputs to prevent unclaimable slots
-        require!(token_mint.is_some(), GhostError::WrongMint);
+        // v1.11: token_mint = None is a native SOL slot — only Transfer is executable
         require!(action <= 1, GhostError::InvalidBeneficiaryAction);
+        require!(token_mint.is_some() || action == 0, GhostError::InvalidBeneficiaryAction);
         require!((index as usize) < ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+        require!(!ghost.is_v111() || ghost.distinct_mints_with(token_mint, Some(index as usize), None) <= MAX_SNAPSHOT_MINTS, GhostError::TooManySnapshotMints);
+        // v1.11: a share-mode slot moving to another mint must fit under that mint's cap
+        let share_bps = if ghost.is_v111() { ghost.beneficiary_ext[index as usize].share_bps as u64 } else { 0 };
+        if share_bps > 0 {
+            require!(share_bps_for_mint(ghost, token_mint, index as usize) + share_bps <= MAX_SHARE_BPS, GhostError::ShareAllocationExceeded);
+        }
+        // v1.11: vesting is only defined for token Transfer slots
+        if ghost.is_v111() && ghost.beneficiary_ext[index as usize].is_vesting() {
+            require!(action == 0 && token_mint.is_some(), GhostError::InvalidVestingSchedule);
+        }
+        if ghost.is_v111() && ghost.beneficiary_ext[index as usize].has_contingent() {
+            require!(action == 0 && token_mint.is_some(), GhostError::InvalidContingentRecipient);
+            require!(ghost.beneficiary_ext[index as usize].contingent_recipient != Some(recipient), GhostError::InvalidContingentRecipient);
+        }
         let slot = &mut ghost.beneficiaries[index as usize];
         let old_recipient = slot.recipient;
         let old_amount = slot.amount;
@@ -293,14 +1132,139 @@ pub mod ghost_protocol {
         Ok(())
     }
 
+    // v1.11: switch a slot between absolute mode (share_bps = 0, pays `amount`) and share
+    // mode (pays share_bps / 10,000 of the vault balance for its mint, resolved at
+    // execute_legacy). Shares across all slots of one mint are capped at 10,000 bps.
+    pub fn set_beneficiary_share(ctx: Context<ManageBeneficiaries>, index: u8, share_bps: u16) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        let clock = Clock::get()?;
+        require!(!ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
+        require!((index as usize) < ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+        let mint = ghost.beneficiaries[index as usize].token_mint;
+        require!(share_bps_for_mint(ghost, mint, index as usize) + share_bps as u64 <= MAX_SHARE_BPS, GhostError::ShareAllocationExceeded);
+        ghost.beneficiary_ext[index as usize].share_bps = share_bps;
+        emit!(BeneficiaryShareSet { soul: ghost.owner, index, share_bps });
+        msg!("Beneficiary at index {} share set to {} bps", index, share_bps);
+        Ok(())
+    }
+
+    // v1.11: release a token Transfer slot over time after execution instead of in one
+    // payment. All zeros turns vesting off.
+    pub fn set_beneficiary_vesting(ctx: Context<ManageBeneficiaries>, index: u8, cliff_seconds: i64, duration_seconds: i64, tranches: u16) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        let clock = Clock::get()?;
+        require!(!ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
+        require!((index as usize) < ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+        require!(cliff_seconds >= 0 && duration_seconds >= 0, GhostError::InvalidVestingSchedule);
+        require!(tranches == 0 || duration_seconds > 0, GhostError::InvalidVestingSchedule);
+        let vesting = cliff_seconds > 0 || duration_seconds > 0;
+        let beneficiary = ghost.beneficiaries[index as usize];
+        require!(!vesting || (beneficiary.action == 0 && beneficiary.token_mint.is_some()), GhostError::InvalidVestingSchedule);
+        require!(!vesting || !ghost.beneficiary_ext[index as usize].has_contingent(), GhostError::InvalidVestingSchedule);
+        let ext = &mut ghost.beneficiary_ext[index as usize];
+        ext.vesting_cliff_seconds = cliff_seconds;
+        ext.vesting_duration_seconds = duration_seconds;
+        ext.vesting_tranches = tranches;
+        emit!(BeneficiaryVestingSet { soul: ghost.owner, index, cliff_seconds, duration_seconds, tranches });
+        msg!("Beneficiary at index {} vesting: cliff {}s, duration {}s, {} tranches", index, cliff_seconds, duration_seconds, tranches);
+        Ok(())
+    }
+
+    // v1.11: fallback recipient for a slot whose primary never claims. `None` removes it.
+    pub fn set_beneficiary_contingent(ctx: Context<ManageBeneficiaries>, index: u8, contingent_recipient: Option<Pubkey>, after_seconds: i64) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        let clock = Clock::get()?;
+        require!(!ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
+        require!((index as usize) < ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+        let beneficiary = ghost.beneficiaries[index as usize];
+        if let Some(contingent) = contingent_recipient {
+            require!(beneficiary.action == 0 && beneficiary.token_mint.is_some(), GhostError::InvalidContingentRecipient);
+            require!(contingent != beneficiary.recipient && contingent != Pubkey::default(), GhostError::InvalidContingentRecipient);
+            require!(after_seconds > 0, GhostError::InvalidContingentRecipient);
+            require!(!ghost.beneficiary_ext[index as usize].is_vesting(), GhostError::InvalidContingentRecipient);
+        }
+        let after_seconds = if contingent_recipient.is_some() { after_seconds } else { 0 };
+        let ext = &mut ghost.beneficiary_ext[index as usize];
+        ext.contingent_recipient = contingent_recipient;
+        ext.contingent_after_seconds = after_seconds;
+        emit!(BeneficiaryContingentSet { soul: ghost.owner, index, contingent_recipient, after_seconds });
+        msg!("Beneficiary at index {} contingent recipient set", index);
+        Ok(())
+    }
+
+    // v1.11: a beneficiary slot in its own PDA, unbounded by MAX_BENEFICIARIES. The owner
+    // pays its rent. Ids are never reused, so a removed id stays dead.
+    pub fn add_beneficiary_account(ctx: Context<AddBeneficiaryAccount>, recipient: Pubkey, amount: u64, token_mint: Option<Pubkey>, action: u8) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        let clock = Clock::get()?;
+        require!(!ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
+        require!(action <= 1, GhostError::InvalidBeneficiaryAction);
+        require!(token_mint.is_some() || action == 0, GhostError::InvalidBeneficiaryAction);
+        // A mint the snapshot can't hold would fail its tally and block every payout
+        require!(ghost.distinct_mints_with(token_mint, None, None) <= MAX_SNAPSHOT_MINTS, GhostError::TooManySnapshotMints);
+        ghost.track_account_mint(token_mint)?;
+        let account = &mut ctx.accounts.beneficiary_account;
+        account.ghost = ghost.key();
+        account.id = ghost.next_beneficiary_id;
+        account.beneficiary = Beneficiary { recipient, amount, token_mint, action, executed: false };
+        account.ext = BeneficiaryExt::default();
+        account.tallied = false;
+        account.bump = ctx.bumps.beneficiary_account;
+        ghost.next_beneficiary_id = ghost.next_beneficiary_id.checked_add(1).ok_or(GhostError::Overflow)?;
+        ghost.beneficiary_accounts += 1;
+        emit!(BeneficiaryAdded { soul: ghost.owner, recipient, amount, action });
+        msg!("Beneficiary account #{} added: {} receives {}", account.id, recipient, amount);
+        Ok(())
+    }
+
+    pub fn update_beneficiary_account(ctx: Context<ManageBeneficiaryAccount>, recipient: Pubkey, amount: u64, token_mint: Option<Pubkey>, action: u8) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        let clock = Clock::get()?;
+        require!(!ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
+        require!(action <= 1, GhostError::InvalidBeneficiaryAction);
+        require!(token_mint.is_some() || action == 0, GhostError::InvalidBeneficiaryAction);
+        let old_mint = ctx.accounts.beneficiary_account.beneficiary.token_mint;
+        if token_mint != old_mint {
+            require!(ghost.distinct_mints_with(token_mint, None, Some(old_mint)) <= MAX_SNAPSHOT_MINTS, GhostError::TooManySnapshotMints);
+            ghost.untrack_account_mint(old_mint);
+            ghost.track_account_mint(token_mint)?;
+        }
+        let account = &mut ctx.accounts.beneficiary_account;
+        let old_recipient = account.beneficiary.recipient;
+        let old_amount = account.beneficiary.amount;
+        account.beneficiary = Beneficiary { recipient, amount, token_mint, action, executed: false };
+        emit!(BeneficiaryAccountUpdated { soul: ghost.owner, id: account.id, old_recipient, new_recipient: recipient, old_amount, new_amount: amount, action });
+        msg!("Beneficiary account #{} updated", account.id);
+        Ok(())
+    }
+
+    // Closes the PDA and refunds its rent to the owner.
+    pub fn remove_beneficiary_account(ctx: Context<RemoveBeneficiaryAccount>) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        ghost.beneficiary_accounts = ghost.beneficiary_accounts.saturating_sub(1);
+        ghost.untrack_account_mint(ctx.accounts.beneficiary_account.beneficiary.token_mint);
+        emit!(BeneficiaryAccountRemoved { soul: ghost.owner, id: ctx.accounts.beneficiary_account.id });
+        msg!("Beneficiary account #{} removed", ctx.accounts.beneficiary_account.id);
+        Ok(())
+    }
+
     pub fn guardian_remove_beneficiary(ctx: Context<GuardianManageBeneficiaries>, index: u8) -> Result<()> {
         let ghost = &mut ctx.accounts.ghost;
         require!(is_recovery_wallet(&ghost.recovery_wallets, ctx.accounts.recovery_wallet.key()), GhostError::Unauthorized);
+        // v1.11: with a quorum or time-lock configured, guardians must go through proposals
+        require!(ghost.guardian_actions_direct(), GhostError::GuardianProposalRequired);
         require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
-        require!((index as usize) < ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
-        let count = ghost.beneficiary_count as usize;
-        for i in (index as usize)..(count - 1) { ghost.beneficiaries[i] = ghost.beneficiaries[i + 1]; }
-        ghost.beneficiary_count -= 1;
+        ghost.remove_beneficiary_at(index)?;
         msg!("Guardian removed beneficiary at index {}", index);
         Ok(())
     }
@@ -308,10 +1272,9 @@ pub mod ghost_protocol {
     pub fn guardian_clear_beneficiaries(ctx: Context<GuardianManageBeneficiaries>) -> Result<()> {
         let ghost = &mut ctx.accounts.ghost;
         require!(is_recovery_wallet(&ghost.recovery_wallets, ctx.accounts.recovery_wallet.key()), GhostError::Unauthorized);
+        require!(ghost.guardian_actions_direct(), GhostError::GuardianProposalRequired);
         require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
-        let cleared_count = ghost.beneficiary_count;
-        for i in 0..MAX_BENEFICIARIES { ghost.beneficiaries[i] = Beneficiary::default(); }
-        ghost.beneficiary_count = 0;
+        let cleared_count = ghost.clear_beneficiaries();
         emit!(BeneficiariesCleared { soul: ghost.owner, cleared_by: ctx.accounts.recovery_wallet.key(), count: cleared_count });
         msg!("Guardian cleared {} beneficiaries", cleared_count);
         Ok(())
@@ -320,12 +1283,11 @@ pub mod ghost_protocol {
     pub fn guardian_set_whole_vault_recipient(ctx: Context<GuardianManageBeneficiaries>, recipient: Option<Pubkey>, action: u8) -> Result<()> {
         let ghost = &mut ctx.accounts.ghost;
         require!(is_recovery_wallet(&ghost.recovery_wallets, ctx.accounts.recovery_wallet.key()), GhostError::Unauthorized);
+        require!(ghost.guardian_actions_direct(), GhostError::GuardianProposalRequired);
         require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
         // v1.10: validate action — only 0 (Transfer) or 1 (Burn) are executable
         require!(action <= 1, GhostError::InvalidBeneficiaryAction);
-        let previous = ghost.whole_vault_recipient;
-        ghost.whole_vault_recipient = recipient;
-        ghost.whole_vault_action = if recipient.is_some() { action } else { 0 };
+        let previous = ghost.set_whole_vault(recipient, action);
         emit!(WholeVaultRecipientSet { soul: ghost.owner, recipient, cleared: recipient.is_none(), previous });
         msg!("Guardian updated whole vault recipient — action: {}", action);
         Ok(())
@@ -337,14 +1299,53 @@ pub mod ghost_protocol {
         require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
         // v1.10: validate action — only 0 (Transfer) or 1 (Burn) are executable
         require!(action <= 1, GhostError::InvalidBeneficiaryAction);
-        let previous = ghost.whole_vault_recipient;
-        ghost.whole_vault_recipient = recipient;
-        ghost.whole_vault_action = if recipient.is_some() { action } else { 0 };
+        let previous = ghost.set_whole_vault(recipient, action);
         emit!(WholeVaultRecipientSet { soul: ghost.owner, recipient, cleared: recipient.is_none(), previous });
         msg!("Whole vault recipient updated — action: {}", action);
         Ok(())
     }
 
+    // v1.11: fallback estate recipient — receives everything left in the vault once every
+    // beneficiary slot has been executed. None clears it.
+    pub fn set_residual_recipient(ctx: Context<UpdateSettings>, recipient: Option<Pubkey>) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        let previous = ghost.residual_recipient;
+        ghost.residual_recipient = recipient;
+        emit!(ResidualRecipientSet { soul: ghost.owner, recipient, previous });
+        msg!("Residual recipient updated");
+        Ok(())
+    }
+
+    // v1.11: where a disclaimed slot goes — DISCLAIM_POLICY_ESTATE or DISCLAIM_POLICY_PRO_RATA.
+    pub fn set_disclaim_policy(ctx: Context<UpdateSettings>, policy: u8) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!(policy <= DISCLAIM_POLICY_PRO_RATA, GhostError::InvalidDisclaimPolicy);
+        ghost.disclaim_policy = policy;
+        emit!(DisclaimPolicySet { soul: ghost.owner, policy });
+        msg!("Disclaim policy set to {}", policy);
+        Ok(())
+    }
+
+    // v1.11: receiver of the rent and leftover stake freed by finalization. None falls back
+    // to the estate recipient (see GhostAccount::rent_destination).
+    pub fn set_rent_recipient(ctx: Context<UpdateSettings>, recipient: Option<Pubkey>) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        let previous = ghost.rent_recipient;
+        ghost.rent_recipient = recipient;
+        emit!(RentRecipientSet { soul: ghost.owner, recipient, previous });
+        msg!("Rent recipient updated");
+        Ok(())
+    }
+
     pub fn set_ghost_profile(ctx: Context<UpdateSettings>, display_name: [u8; 32], image_uri: [u8; 128]) -> Result<()> {
         let ghost = &mut ctx.accounts.ghost;
         ghost.display_name = display_name;
@@ -353,14 +1354,20 @@ pub mod ghost_protocol {
         Ok(())
     }
 
-    pub fn pause_ghost(ctx: Context<UpdateSettings>) -> Result<()> {
+    // v1.11: a pause always ends — at `until`, no more than config.max_pause_seconds ahead.
+    // A lapsed pause may be replaced by a new one.
+    pub fn pause_ghost(ctx: Context<PauseGhost>, until: i64) -> Result<()> {
+        let clock = Clock::get()?;
+        let max_pause_seconds = ctx.accounts.config.max_pause_seconds;
         let ghost = &mut ctx.accounts.ghost;
-        require!(!ghost.paused, GhostError::GhostPausedError);
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
         require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!(until > clock.unix_timestamp && until - clock.unix_timestamp <= max_pause_seconds, GhostError::InvalidPauseDuration);
         ghost.paused = true;
-        let clock = Clock::get()?;
-        emit!(GhostPaused { soul: ghost.owner, timestamp: clock.unix_timestamp });
-        msg!("Ghost paused by owner");
+        ghost.paused_until = until;
+        emit!(GhostPaused { soul: ghost.owner, timestamp: clock.unix_timestamp, until });
+        msg!("Ghost paused by owner until {}", until);
         Ok(())
     }
 
@@ -369,40 +1376,100 @@ pub mod ghost_protocol {
         require!(ghost.paused, GhostError::GhostNotPaused);
         ghost.paused = false;
         let clock = Clock::get()?;
+        // v1.11: resuming early ends the pause now — silence is counted from here
+        if ghost.is_v111() {
+            ghost.paused_until = ghost.paused_until.min(clock.unix_timestamp);
+        }
         emit!(GhostResumed { soul: ghost.owner, timestamp: clock.unix_timestamp });
         msg!("Ghost resumed by owner");
         Ok(())
     }
 
-    // v1.10: transfer_ownership and accept_ownership REMOVED. Ghosts are soulbound.
-    // Reason: PDA address is permanently fixed at [GHOST_SEED, original_owner.key()] at
-    // init time. Any change to ghost.owner could not be reflected in the PDA address,
-    // which permanently bricked all signer-derived owner-mutating instructions
-    // (Ping, ManageBeneficiaries, Deposit/Withdraw, UpdateSettings, AbandonGhost,
-    // MigrateGhost) — the new owner couldn't pass the seed check, the old owner
-    // couldn't pass the constraint check, ghost was stuck.
-    //
-    // Restoring this feature would require adding `original_owner: Pubkey` as a stable
-    // PDA-seed field separate from `ghost.owner` — a struct change requiring v1.10
-    // schema migration. Defer to a future version if the feature is actually needed.
-    //
-    // The struct field `pending_owner` and events `OwnershipTransferInitiated` /
-    // `OwnershipTransferAccepted` are KEPT to preserve byte layout. The error variant
-    // `NoPendingOwnerTransfer` is also kept to preserve error code numbering.
+    // v1.11: permissionless — clears the flag of a pause whose `until` has passed, so
+    // watchers see the lapse on-chain without waiting for check_silence.
+    pub fn expire_pause(ctx: Context<ExpirePause>) -> Result<()> {
+        let clock = Clock::get()?;
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.paused, GhostError::GhostNotPaused);
+        require!(!ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
+        ghost.paused = false;
+        emit!(GhostPauseLapsed { soul: ghost.owner, paused_until: ghost.paused_until, timestamp: clock.unix_timestamp });
+        msg!("Pause lapsed at {}", ghost.paused_until);
+        Ok(())
+    }
 
-    pub fn update_interval_and_grace(ctx: Context<UpdateSettings>, interval_seconds: i64, grace_period_seconds: i64) -> Result<()> {
-        require!(interval_seconds >= MIN_INTERVAL, GhostError::IntervalTooShort);
-        require!(grace_period_seconds >= MIN_GRACE_PERIOD, GhostError::GracePeriodTooShort);
-        ctx.accounts.ghost.interval_seconds = interval_seconds;
-        ctx.accounts.ghost.grace_period_seconds = grace_period_seconds;
-        msg!("Interval set to {}s, grace period set to {}s", interval_seconds, grace_period_seconds);
+    // v1.11: transfer_ownership / accept_ownership restored. v1.10 removed them because the
+    // PDAs were derived from ghost.owner, so changing it locked everyone out. Every Accounts
+    // struct now derives from ghost.seed_owner() (the stable `original_owner`), so `owner`
+    // is free to change. Two-step with a cooldown: a key that is about to be lost or was
+    // leaked can hand off, and the owner has OWNERSHIP_TRANSFER_COOLDOWN_SECONDS to cancel
+    // a transfer they did not start. Requires a migrated account.
+    pub fn transfer_ownership(ctx: Context<UpdateSettings>, new_owner: Pubkey) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!(new_owner != ghost.owner && new_owner != Pubkey::default(), GhostError::InvalidNewOwner);
+        let clock = Clock::get()?;
+        ghost.pending_owner = Some(new_owner);
+        ghost.pending_owner_since = clock.unix_timestamp;
+        emit!(OwnershipTransferInitiated { soul: ghost.owner, pending_owner: new_owner, timestamp: clock.unix_timestamp });
+        msg!("Ownership transfer to {} opened — acceptable after {}s", new_owner, OWNERSHIP_TRANSFER_COOLDOWN_SECONDS);
         Ok(())
     }
 
-    pub fn update_interval(ctx: Context<UpdateSettings>, interval_seconds: i64) -> Result<()> {
-        require!(interval_seconds >= MIN_INTERVAL, GhostError::IntervalTooShort);
-        ctx.accounts.ghost.interval_seconds = interval_seconds;
-        msg!("Interval updated to {}s", interval_seconds);
+    pub fn cancel_ownership_transfer(ctx: Context<UpdateSettings>) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        let pending_owner = ghost.pending_owner.take().ok_or(GhostError::NoPendingOwnerTransfer)?;
+        ghost.pending_owner_since = 0;
+        let clock = Clock::get()?;
+        emit!(OwnershipTransferCancelled { soul: ghost.owner, pending_owner, timestamp: clock.unix_timestamp });
+        msg!("Ownership transfer to {} cancelled", pending_owner);
+        Ok(())
+    }
+
+    pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        let clock = Clock::get()?;
+        require!(clock.unix_timestamp >= ghost.pending_owner_since + OWNERSHIP_TRANSFER_COOLDOWN_SECONDS, GhostError::OwnershipTransferCooldown);
+        let old_owner = ghost.owner;
+        ghost.owner = ctx.accounts.new_owner.key();
+        ghost.pending_owner = None;
+        ghost.pending_owner_since = 0;
+        // Accepting is a signed act by the new owner — counts as a heartbeat
+        ghost.last_heartbeat = clock.unix_timestamp;
+        // The previous owner's delegates and the guardian actions queued under them don't carry over
+        for delegate in std::mem::take(&mut ghost.ping_delegates) {
+            if delegate.key != Pubkey::default() {
+                emit!(PingDelegateRemoved { soul: ghost.owner, delegate: delegate.key });
+            }
+        }
+        let vetoed = ghost.record_owner_ping();
+        if vetoed > 0 {
+            emit!(GuardianActionVetoed { soul: ghost.owner, proposal_id: None, count: vetoed, timestamp: clock.unix_timestamp });
+        }
+        emit!(OwnershipTransferAccepted { old_owner, new_owner: ghost.owner, timestamp: clock.unix_timestamp });
+        msg!("Ownership transferred from {} to {}", old_owner, ghost.owner);
+        Ok(())
+    }
+
+    pub fn update_interval_and_grace(ctx: Context<UpdateSettings>, interval_seconds: i64, grace_period_seconds: i64) -> Result<()> {
+        require!(interval_seconds >= MIN_INTERVAL, GhostError::IntervalTooShort);
+        require!(grace_period_seconds >= MIN_GRACE_PERIOD, GhostError::GracePeriodTooShort);
+        ctx.accounts.ghost.interval_seconds = interval_seconds;
+        ctx.accounts.ghost.grace_period_seconds = grace_period_seconds;
+        msg!("Interval set to {}s, grace period set to {}s", interval_seconds, grace_period_seconds);
+        Ok(())
+    }
+
+    pub fn update_interval(ctx: Context<UpdateSettings>, interval_seconds: i64) -> Result<()> {
+        require!(interval_seconds >= MIN_INTERVAL, GhostError::IntervalTooShort);
+        ctx.accounts.ghost.interval_seconds = interval_seconds;
+        msg!("Interval updated to {}s", interval_seconds);
         Ok(())
     }
 
@@ -422,57 +1489,307 @@ pub mod ghost_protocol {
         require!(!ctx.accounts.ghost.executed, GhostError::GhostAlreadyExecuted);
         require!((index as usize) < MAX_RECOVERY_WALLETS, GhostError::InvalidRecoveryWalletIndex);
         ctx.accounts.ghost.recovery_wallets[index as usize] = wallet;
+        // v1.11: never leave a quorum that the remaining guardians cannot reach
+        require!(ctx.accounts.ghost.guardian_quorum() <= ctx.accounts.ghost.guardian_count().max(1), GhostError::InvalidGuardianThreshold);
         msg!("Recovery wallet slot {} updated", index);
         Ok(())
     }
 
+    // v1.11: number of recovery wallet approvals every guardian action needs. 1 keeps the
+    // legacy single-guardian instructions; above 1 routes everything through proposals.
+    pub fn set_guardian_threshold(ctx: Context<UpdateSettings>, threshold: u8) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!(threshold >= 1 && threshold <= ghost.guardian_count().max(1), GhostError::InvalidGuardianThreshold);
+        ghost.guardian_threshold = threshold;
+        emit!(GuardianThresholdSet { soul: ghost.owner, threshold, guardians: ghost.guardian_count() });
+        msg!("Guardian threshold set to {}-of-{}", threshold, ghost.guardian_count());
+        Ok(())
+    }
+
+    // v1.11: how long a guardian action sits in the queue (owner veto window) after quorum.
+    pub fn set_guardian_delay(ctx: Context<UpdateSettings>, delay_seconds: i64) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!((0..=MAX_GUARDIAN_DELAY_SECONDS).contains(&delay_seconds), GhostError::InvalidGuardianDelay);
+        ghost.guardian_delay_seconds = delay_seconds;
+        emit!(GuardianDelaySet { soul: ghost.owner, delay_seconds });
+        msg!("Guardian action delay set to {}s", delay_seconds);
+        Ok(())
+    }
+
+    // v1.11: open a guardian proposal. The proposer's approval is counted immediately.
+    pub fn propose_guardian_action(ctx: Context<ProposeGuardianAction>, action: GuardianAction) -> Result<()> {
+        let clock = Clock::get()?;
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        let guardian_index = recovery_wallet_index(&ghost.recovery_wallets, ctx.accounts.guardian.key()).ok_or(GhostError::Unauthorized)?;
+        match action {
+            GuardianAction::RecoveryWithdraw { amount, .. } => require!(amount > 0, GhostError::InvalidGuardianAction),
+            GuardianAction::RemoveBeneficiary { index, recipient, token_mint } => {
+                require!((index as usize) < ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+                require!(ghost.slot_matches(index, recipient, token_mint), GhostError::BeneficiaryChanged);
+            }
+            GuardianAction::ClearBeneficiaries => {}
+            GuardianAction::SetWholeVaultRecipient { action, .. } => require!(action <= 1, GhostError::InvalidBeneficiaryAction),
+            GuardianAction::AttestDeath { remaining_seconds } => require!(remaining_seconds >= 0, GhostError::InvalidGuardianAction),
+        }
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.ghost = ghost.key();
+        proposal.id = ghost.proposal_count;
+        proposal.proposer = ctx.accounts.guardian.key();
+        proposal.action = action;
+        proposal.guardians = ghost.recovery_wallets;
+        proposal.approvals = 1 << guardian_index;
+        proposal.created_at = clock.unix_timestamp;
+        proposal.expires_at = clock.unix_timestamp + PROPOSAL_TTL_SECONDS;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+        proposal.ping_nonce = ghost.owner_ping_nonce;
+        proposal.executable_at = None;
+        proposal.vetoed = false;
+        ghost.proposal_count += 1;
+        emit!(GuardianActionProposed { soul: ghost.owner, proposal_id: proposal.id, proposer: proposal.proposer, expires_at: proposal.expires_at });
+        msg!("Guardian proposal #{} opened", proposal.id);
+        // 1-of-N: the proposer's own approval already reaches quorum
+        proposal.queue_if_quorum(ghost, clock.unix_timestamp);
+        Ok(())
+    }
+
+    pub fn approve_guardian_action(ctx: Context<ApproveGuardianAction>) -> Result<()> {
+        let clock = Clock::get()?;
+        let ghost = &mut ctx.accounts.ghost;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, GhostError::ProposalAlreadyExecuted);
+        require!(clock.unix_timestamp <= proposal.expires_at, GhostError::ProposalExpired);
+        require!(proposal.guardians == ghost.recovery_wallets, GhostError::GuardianSetChanged);
+        require!(!proposal.vetoed && proposal.ping_nonce == ghost.owner_ping_nonce, GhostError::GuardianActionVetoed);
+        let guardian_index = recovery_wallet_index(&ghost.recovery_wallets, ctx.accounts.guardian.key()).ok_or(GhostError::Unauthorized)?;
+        require!(proposal.approvals & (1 << guardian_index) == 0, GhostError::AlreadyApproved);
+        proposal.approvals |= 1 << guardian_index;
+        let approvals = proposal.approvals.count_ones() as u8;
+        emit!(GuardianActionApproved { soul: ghost.owner, proposal_id: proposal.id, guardian: ctx.accounts.guardian.key(), approvals });
+        msg!("Guardian proposal #{} approved ({}/{})", proposal.id, approvals, ghost.guardian_quorum());
+        proposal.queue_if_quorum(ghost, clock.unix_timestamp);
+        Ok(())
+    }
+
+    // v1.11: carry out a proposal that reached quorum. Any recovery wallet may submit it.
+    // RecoveryWithdraw needs the optional vault/token accounts; they're checked against the
+    // proposal here because their expected addresses depend on the proposal contents.
+    pub fn execute_guardian_action(ctx: Context<ExecuteGuardianAction>) -> Result<()> {
+        let clock = Clock::get()?;
+        let guardian = ctx.accounts.guardian.key();
+        let action = ctx.accounts.proposal.action;
+        {
+            let ghost = &ctx.accounts.ghost;
+            let proposal = &ctx.accounts.proposal;
+            require!(is_recovery_wallet(&ghost.recovery_wallets, guardian), GhostError::Unauthorized);
+            require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+            require!(!proposal.executed, GhostError::ProposalAlreadyExecuted);
+            require!(clock.unix_timestamp <= proposal.expires_at, GhostError::ProposalExpired);
+            require!(proposal.guardians == ghost.recovery_wallets, GhostError::GuardianSetChanged);
+            require!(proposal.approvals.count_ones() as u8 >= ghost.guardian_quorum(), GhostError::QuorumNotReached);
+            // v1.11: time-lock — queued at quorum; an owner veto (instruction or ping) kills it
+            require!(!proposal.vetoed && proposal.ping_nonce == ghost.owner_ping_nonce, GhostError::GuardianActionVetoed);
+            let executable_at = proposal.executable_at.ok_or(GhostError::QuorumNotReached)?;
+            require!(clock.unix_timestamp >= executable_at, GhostError::GuardianActionTimeLocked);
+        }
+        let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
+
+        match action {
+            GuardianAction::RecoveryWithdraw { mint, amount, destination } => {
+                let (Some(vault), Some(token_mint), Some(vault_token_account), Some(destination_token_account), Some(token_program)) = (
+                    ctx.accounts.vault.as_ref(),
+                    ctx.accounts.token_mint.as_ref(),
+                    ctx.accounts.vault_token_account.as_ref(),
+                    ctx.accounts.destination_token_account.as_ref(),
+                    ctx.accounts.token_program.as_ref(),
+                ) else {
+                    return err!(GhostError::ProposalAccountMismatch);
+                };
+                let vault_bump = ctx.accounts.ghost.vault_bump;
+                let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+                let expected_vault = Pubkey::create_program_address(seeds, &crate::ID).map_err(|_| GhostError::ProposalAccountMismatch)?;
+                require!(vault.key() == expected_vault, GhostError::ProposalAccountMismatch);
+                require!(token_mint.key() == mint, GhostError::ProposalAccountMismatch);
+                require!(vault_token_account.mint == mint && vault_token_account.owner == expected_vault, GhostError::ProposalAccountMismatch);
+                require!(destination_token_account.key() == destination && destination_token_account.mint == mint, GhostError::ProposalAccountMismatch);
+                let signer_seeds = &[&seeds[..]];
+                let cpi_ctx = CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    TransferChecked { from: vault_token_account.to_account_info(), to: destination_token_account.to_account_info(), authority: vault.to_account_info(), mint: token_mint.to_account_info() },
+                    signer_seeds,
+                );
+                token_interface::transfer_checked(cpi_ctx, amount, token_mint.decimals)?;
+                emit!(RecoveryWithdrawal { soul: owner, recovery_wallet: guardian, amount });
+            }
+            GuardianAction::RemoveBeneficiary { index, recipient, token_mint } => {
+                // Owner edits during the delay can shift slots — only remove the one approved
+                require!(ctx.accounts.ghost.slot_matches(index, recipient, token_mint), GhostError::BeneficiaryChanged);
+                ctx.accounts.ghost.remove_beneficiary_at(index)?;
+            }
+            GuardianAction::ClearBeneficiaries => {
+                let count = ctx.accounts.ghost.clear_beneficiaries();
+                emit!(BeneficiariesCleared { soul: owner, cleared_by: guardian, count });
+            }
+            GuardianAction::SetWholeVaultRecipient { recipient, action } => {
+                let previous = ctx.accounts.ghost.set_whole_vault(recipient, action);
+                emit!(WholeVaultRecipientSet { soul: owner, recipient, cleared: recipient.is_none(), previous });
+            }
+            GuardianAction::AttestDeath { remaining_seconds } => {
+                let execute_after = ctx.accounts.ghost.record_attestation(remaining_seconds, clock.unix_timestamp);
+                emit!(DeathAttested { soul: owner, proposal_id: ctx.accounts.proposal.id, executed_by: guardian, attested_at: clock.unix_timestamp, execute_after });
+            }
+        }
+
+        let ghost = &mut ctx.accounts.ghost;
+        ghost.queued_guardian_actions = ghost.queued_guardian_actions.saturating_sub(1);
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.executed = true;
+        emit!(GuardianActionExecuted { soul: owner, proposal_id: proposal.id, executed_by: guardian });
+        msg!("Guardian proposal #{} executed", proposal.id);
+        Ok(())
+    }
+
+    // v1.11: owner veto of a single pending or queued guardian action.
+    pub fn veto_guardian_action(ctx: Context<VetoGuardianAction>) -> Result<()> {
+        let clock = Clock::get()?;
+        let ghost = &mut ctx.accounts.ghost;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, GhostError::ProposalAlreadyExecuted);
+        require!(!proposal.vetoed, GhostError::GuardianActionVetoed);
+        if proposal.is_queued(ghost) {
+            ghost.queued_guardian_actions = ghost.queued_guardian_actions.saturating_sub(1);
+        }
+        proposal.vetoed = true;
+        emit!(GuardianActionVetoed { soul: ghost.owner, proposal_id: Some(proposal.id), count: 1, timestamp: clock.unix_timestamp });
+        msg!("Guardian proposal #{} vetoed by owner", proposal.id);
+        Ok(())
+    }
+
+    // v1.11: permissionless cleanup — closes an executed, vetoed or expired proposal and
+    // returns its rent to the guardian who opened it.
+    pub fn expire_guardian_proposal(ctx: Context<ExpireGuardianProposal>) -> Result<()> {
+        let clock = Clock::get()?;
+        let ghost = &mut ctx.accounts.ghost;
+        let proposal = &ctx.accounts.proposal;
+        let vetoed = proposal.vetoed || proposal.ping_nonce != ghost.owner_ping_nonce;
+        require!(proposal.executed || vetoed || clock.unix_timestamp > proposal.expires_at, GhostError::ProposalStillActive);
+        if proposal.is_queued(ghost) {
+            ghost.queued_guardian_actions = ghost.queued_guardian_actions.saturating_sub(1);
+        }
+        emit!(GuardianProposalClosed { soul: ghost.owner, proposal_id: proposal.id, executed: proposal.executed });
+        msg!("Guardian proposal #{} closed", proposal.id);
+        Ok(())
+    }
+
+    // v1.11: if a warning stage is set, issue_warning must have run in this silence first.
     pub fn check_silence(ctx: Context<CheckSilence>) -> Result<()> {
         let clock = Clock::get()?;
         let owner = ctx.accounts.ghost.owner;
         let awakened = ctx.accounts.ghost.awakened;
         let executed = ctx.accounts.ghost.executed;
-        let last_heartbeat = ctx.accounts.ghost.last_heartbeat;
+        let silence_start = ctx.accounts.ghost.silence_start();
         let interval_seconds = ctx.accounts.ghost.interval_seconds;
         let grace_period_seconds = ctx.accounts.ghost.grace_period_seconds;
-        let staked_ghost = ctx.accounts.ghost.staked_ghost;
-        let bump = ctx.accounts.ghost.bump;
         let caller_key = ctx.accounts.caller.key();
         require!(!awakened, GhostError::GhostAlreadyAwakened);
         require!(!executed, GhostError::GhostAlreadyExecuted);
-        require!(!ctx.accounts.ghost.paused, GhostError::GhostPausedError);
-        let silence = clock.unix_timestamp - last_heartbeat;
+        require!(!ctx.accounts.ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
+        // v1.11: silence is counted from the end of the pause, not from the last heartbeat
+        let silence = clock.unix_timestamp - silence_start;
         require!(silence > interval_seconds, GhostError::SoulStillAlive);
-        let bounty = staked_ghost.checked_mul(SILENCE_BOUNTY_BPS).unwrap().checked_div(10_000).unwrap();
+        require!(!ctx.accounts.ghost.has_warning_stage() || ctx.accounts.ghost.warning_issued(), GhostError::AwakeningStageMissing);
+        if ctx.accounts.ghost.paused {
+            ctx.accounts.ghost.paused = false;
+            emit!(GhostPauseLapsed { soul: owner, paused_until: ctx.accounts.ghost.paused_until, timestamp: clock.unix_timestamp });
+        }
         ctx.accounts.ghost.awakened = true;
         ctx.accounts.ghost.awakened_at = Some(clock.unix_timestamp);
-        let seeds = &[GHOST_SEED, owner.as_ref(), &[bump]];
-        let signer_seeds = &[&seeds[..]];
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked {
-                from: ctx.accounts.ghost_stake_vault.to_account_info(),
-                to: ctx.accounts.caller_token_account.to_account_info(),
-                authority: ctx.accounts.ghost.to_account_info(),
-                mint: ctx.accounts.ghost_mint.to_account_info(),
-            },
-            signer_seeds,
-        );
-        token_interface::transfer_checked(cpi_ctx, bounty, ctx.accounts.ghost_mint.decimals)?;
-        ctx.accounts.ghost.staked_ghost = staked_ghost.checked_sub(bounty).unwrap();
+        ctx.accounts.ghost.stage_bounty_base = ctx.accounts.ghost.remaining_stake();
+        let bounty_bps = ctx.accounts.ghost.awakening_bounty_bps();
+        let bounty = pay_stage_bounty(ctx.accounts, bounty_bps)?;
         emit!(GhostAwakened { soul: owner, silence_duration: silence, awakened_at: clock.unix_timestamp, grace_period_ends: clock.unix_timestamp + grace_period_seconds, bounty_paid: bounty, caller: caller_key });
         msg!("Ghost awakened! Grace period: {}s", grace_period_seconds);
         Ok(())
     }
 
+    // v1.11: owner-chosen escalation stages, in basis points of the interval (warning) and
+    // of the grace period (final notice). 0 disables a stage.
+    pub fn set_awakening_stages(ctx: Context<UpdateSettings>, warning_bps: u16, final_notice_bps: u16) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!(warning_bps < 10_000 && final_notice_bps < 10_000, GhostError::InvalidAwakeningStages);
+        ghost.warning_bps = warning_bps;
+        ghost.final_notice_bps = final_notice_bps;
+        emit!(AwakeningStagesSet { soul: ghost.owner, warning_bps, final_notice_bps });
+        msg!("Awakening stages: warning at {} bps of interval, final notice at {} bps of grace", warning_bps, final_notice_bps);
+        Ok(())
+    }
+
+    // v1.11: permissionless — records the warning stage once warning_bps of the interval has
+    // passed in silence. Unpaid: a heartbeat may still follow, so check_silence keeps the slice.
+    pub fn issue_warning(ctx: Context<CheckSilence>) -> Result<()> {
+        let clock = Clock::get()?;
+        let ghost = &ctx.accounts.ghost;
+        require!(ghost.has_warning_stage(), GhostError::AwakeningStageNotEnabled);
+        require!(!ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!(!ghost.is_paused(clock.unix_timestamp), GhostError::GhostPausedError);
+        require!(!ghost.warning_issued(), GhostError::AwakeningStageRecorded);
+        let silence = clock.unix_timestamp - ghost.silence_start();
+        let threshold = (ghost.interval_seconds as i128 * ghost.warning_bps as i128 / 10_000) as i64;
+        require!(silence > threshold, GhostError::AwakeningStageNotDue);
+        let owner = ghost.owner;
+        let awakens_at = ghost.silence_start() + ghost.interval_seconds;
+        ctx.accounts.ghost.warned_at = clock.unix_timestamp;
+        emit!(GhostWarned { soul: owner, silence_duration: silence, awakens_at, caller: ctx.accounts.caller.key() });
+        msg!("Ghost warned after {}s of silence", silence);
+        Ok(())
+    }
+
+    // v1.11: permissionless — records the final notice once final_notice_bps of the grace
+    // period has passed. Pays FINAL_NOTICE_BOUNTY_BPS of the awakening's stage_bounty_base.
+    pub fn issue_final_notice(ctx: Context<CheckSilence>) -> Result<()> {
+        let clock = Clock::get()?;
+        let ghost = &ctx.accounts.ghost;
+        require!(ghost.has_final_notice_stage(), GhostError::AwakeningStageNotEnabled);
+        require!(ghost.awakened, GhostError::GhostNotAwakened);
+        require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!(!ghost.final_notice_issued(), GhostError::AwakeningStageRecorded);
+        let awakened_at = ghost.awakened_at.unwrap();
+        let grace_end = ghost.grace_end();
+        // Measured over the actual window, which an attestation may have shortened
+        let threshold = ((grace_end - awakened_at) as i128 * ghost.final_notice_bps as i128 / 10_000) as i64;
+        require!(clock.unix_timestamp >= awakened_at + threshold, GhostError::AwakeningStageNotDue);
+        let owner = ghost.owner;
+        ctx.accounts.ghost.final_notice_at = clock.unix_timestamp;
+        let bounty = pay_stage_bounty(ctx.accounts, FINAL_NOTICE_BOUNTY_BPS)?;
+        emit!(GhostFinalNotice { soul: owner, grace_period_ends: grace_end, bounty_paid: bounty, caller: ctx.accounts.caller.key() });
+        msg!("Final notice issued — execution possible after {}", grace_end);
+        Ok(())
+    }
+
     pub fn cancel_awakening(ctx: Context<CancelAwakening>) -> Result<()> {
         let ghost = &mut ctx.accounts.ghost;
         require!(ghost.awakened, GhostError::GhostNotAwakened);
         require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
         let clock = Clock::get()?;
-        let grace_end = ghost.awakened_at.unwrap() + ghost.grace_period_seconds;
+        let grace_end = ghost.grace_end();
         require!(clock.unix_timestamp <= grace_end, GhostError::GracePeriodExpired);
         let caller = ctx.accounts.signer.key();
         require!(caller == ghost.owner || is_recovery_wallet(&ghost.recovery_wallets, caller), GhostError::Unauthorized);
+        require!(ghost.may_cancel_awakening(caller == ghost.owner), GhostError::Unauthorized);
         ghost.awakened = false;
         ghost.awakened_at = None;
         ghost.last_heartbeat = clock.unix_timestamp;
@@ -482,12 +1799,43 @@ pub mod ghost_protocol {
     }
 
     pub fn execute_legacy(ctx: Context<ExecuteLegacy>) -> Result<()> {
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let ghost_key = ctx.accounts.ghost.key();
         let ghost = &mut ctx.accounts.ghost;
         require!(ghost.awakened, GhostError::GhostNotAwakened);
         require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
         let clock = Clock::get()?;
-        let grace_end = ghost.awakened_at.unwrap() + ghost.grace_period_seconds;
+        let grace_end = ghost.grace_end();
         require!(clock.unix_timestamp > grace_end, GhostError::GracePeriodActive);
+        require!(!ghost.has_final_notice_stage() || ghost.final_notice_issued(), GhostError::AwakeningStageMissing);
+
+        // v1.11: resolve share-mode slots into absolute amounts. Every balance is read
+        // before any slot can pay out, so all shares see the same vault state. The caller
+        // must supply the vault token account for each beneficiary mint in remaining_accounts —
+        // omitting one fails the instruction rather than silently resolving it to zero.
+        if ghost.is_v111() {
+            for i in 0..ghost.beneficiary_count as usize {
+                let share_bps = ghost.beneficiary_ext[i].share_bps as u64;
+                if share_bps == 0 { continue; }
+                let balance = vault_balance_for_mint(&vault_info, ctx.remaining_accounts, ghost.beneficiaries[i].token_mint)?;
+                let amount = (balance as u128 * share_bps as u128 / MAX_SHARE_BPS as u128) as u64;
+                ghost.beneficiaries[i].amount = amount;
+                emit!(BeneficiaryShareResolved { soul: ghost.owner, index: i as u8, share_bps: share_bps as u16, vault_balance: balance, amount });
+            }
+        }
+
+        // v1.11: freeze per-mint balances and commitments — every payout reads these
+        let mints = build_snapshot_mints(ghost, &vault_info, ctx.remaining_accounts)?;
+        let snapshot = &mut ctx.accounts.snapshot;
+        snapshot.ghost = ghost_key;
+        snapshot.taken_at = clock.unix_timestamp;
+        snapshot.bump = ctx.bumps.snapshot;
+        snapshot.pending_tallies = ghost.beneficiary_account_count();
+        snapshot.accounts_unpaid = 0;
+        snapshot.mints = mints;
+        snapshot.swept = Vec::new();
+        emit!(ExecutionSnapshotTaken { soul: ghost.owner, mint_count: snapshot.mints.len() as u8, taken_at: clock.unix_timestamp });
+
         ghost.executed = true;
         ghost.executed_at = Some(clock.unix_timestamp);
         emit!(LegacyExecuted { soul: ghost.owner, executed_at: clock.unix_timestamp, beneficiary_count: ghost.beneficiary_count });
@@ -495,65 +1843,324 @@ pub mod ghost_protocol {
         Ok(())
     }
 
+    // v1.11: backfill for ghosts executed before v1.11 — execute_legacy took no snapshot,
+    // so payouts would otherwise be blocked. Freezes balances against the slots still
+    // unpaid. Permissionless; `init` makes it one-shot (and fails for v1.11 executions).
+    // remaining_accounts: the vault's canonical ATA for every unpaid slot's mint.
+    pub fn take_execution_snapshot(ctx: Context<TakeExecutionSnapshot>) -> Result<()> {
+        require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
+        let clock = Clock::get()?;
+        let mints = build_snapshot_mints(&ctx.accounts.ghost, &ctx.accounts.vault.to_account_info(), ctx.remaining_accounts)?;
+        let snapshot = &mut ctx.accounts.snapshot;
+        snapshot.ghost = ctx.accounts.ghost.key();
+        snapshot.taken_at = clock.unix_timestamp;
+        snapshot.bump = ctx.bumps.snapshot;
+        snapshot.pending_tallies = ctx.accounts.ghost.beneficiary_account_count();
+        snapshot.accounts_unpaid = 0;
+        snapshot.mints = mints;
+        snapshot.swept = Vec::new();
+        emit!(ExecutionSnapshotTaken { soul: ctx.accounts.ghost.owner, mint_count: snapshot.mints.len() as u8, taken_at: clock.unix_timestamp });
+        msg!("Execution snapshot taken: {} mints", snapshot.mints.len());
+        Ok(())
+    }
+
+    // v1.11: permissionless crank — folds one BeneficiaryAccount into the execution snapshot.
+    // If its mint is not in the snapshot yet, the vault token account for it must be in
+    // remaining_accounts (same contract as execute_legacy). Balances cannot have moved since
+    // execute_legacy: no payout runs while tallies are pending. Beneficiary management keeps
+    // distinct mints within MAX_SNAPSHOT_MINTS, so a new mint always fits.
+    pub fn tally_beneficiary_account(ctx: Context<TallyBeneficiaryAccount>) -> Result<()> {
+        require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
+        let account = &mut ctx.accounts.beneficiary_account;
+        require!(!account.tallied, GhostError::BeneficiaryAlreadyTallied);
+        let snapshot = &mut ctx.accounts.snapshot;
+        require!(snapshot.pending_tallies > 0, GhostError::BeneficiaryAlreadyTallied);
+        let mint = account.beneficiary.token_mint;
+        if snapshot.entry(mint).is_none() {
+            require!(snapshot.mints.len() < MAX_SNAPSHOT_MINTS, GhostError::TooManySnapshotMints);
+            let balance = vault_balance_for_mint(&ctx.accounts.vault.to_account_info(), ctx.remaining_accounts, mint)?;
+            snapshot.mints.push(MintSnapshot { mint, balance, committed: 0, paid: 0, account_outstanding: 0 });
+        }
+        let amount = account.beneficiary.amount;
+        let entry = snapshot.mints.iter_mut().find(|m| m.mint == mint).ok_or(GhostError::MintNotInSnapshot)?;
+        entry.committed = entry.committed.checked_add(amount).ok_or(GhostError::Overflow)?;
+        entry.account_outstanding = entry.account_outstanding.checked_add(amount).ok_or(GhostError::Overflow)?;
+        snapshot.pending_tallies -= 1;
+        snapshot.accounts_unpaid += 1;
+        account.tallied = true;
+        emit!(BeneficiaryAccountTallied { soul: ctx.accounts.ghost.owner, id: account.id, amount, pending_tallies: snapshot.pending_tallies });
+        msg!("Beneficiary account #{} tallied ({} pending)", account.id, snapshot.pending_tallies);
+        Ok(())
+    }
+
+    // v1.11: pays either embedded slot `beneficiary_index` or, when supplied, the tallied
+    // `beneficiary_account` (beneficiary_index is then ignored).
     pub fn execute_transfer(ctx: Context<ExecuteTransfer>, beneficiary_index: u8) -> Result<()> {
         require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
-        require!((beneficiary_index as usize) < ctx.accounts.ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
-        let beneficiary = ctx.accounts.ghost.beneficiaries[beneficiary_index as usize];
+        let beneficiary = match ctx.accounts.beneficiary_account.as_deref() {
+            Some(account) => account.tallied_slot()?,
+            None => {
+                require!((beneficiary_index as usize) < ctx.accounts.ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+                require!(!ctx.accounts.ghost.is_vesting_slot(beneficiary_index as usize), GhostError::VestingSlot);
+                require!(!ctx.accounts.ghost.is_contingent_slot(beneficiary_index as usize), GhostError::ContingentSlot);
+                ctx.accounts.ghost.beneficiaries[beneficiary_index as usize]
+            }
+        };
         require!(!beneficiary.executed, GhostError::BeneficiaryAlreadyPaid);
         require!(beneficiary.action == 0, GhostError::NotATransferBeneficiary);
         require!(beneficiary.recipient == ctx.accounts.recipient.key(), GhostError::WrongRecipient);
         require!(Some(ctx.accounts.token_mint.key()) == beneficiary.token_mint, GhostError::WrongMint);
         let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
         let vault_bump = ctx.accounts.ghost.vault_bump;
-        let seeds = &[VAULT_SEED, owner.as_ref(), &[vault_bump]];
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
         let signer_seeds = &[&seeds[..]];
 
-        // 0.5% protocol fee
-        let fee_amount = beneficiary.amount.checked_mul(EXECUTION_FEE_BPS).unwrap_or(0) / 10_000;
-        let transfer_amount = beneficiary.amount.saturating_sub(fee_amount);
+        // v1.11: slot entitlement from the execution snapshot (pro-rata if the mint is short)
+        let gross_amount = ctx.accounts.snapshot.entitlement(beneficiary.token_mint, beneficiary.amount)?;
 
-        let cpi_ctx = CpiContext::new_with_signer(
+        // Protocol fee at the ghost's locked-in rate
+        let fee_bps = ctx.accounts.ghost.fee_bps(&ctx.accounts.config);
+        let (transfer_amount, fee_amount) = transfer_with_fee(
             ctx.accounts.token_program.to_account_info(),
-            TransferChecked { from: ctx.accounts.vault_token_account.to_account_info(), to: ctx.accounts.recipient_token_account.to_account_info(), authority: ctx.accounts.vault.to_account_info(), mint: ctx.accounts.token_mint.to_account_info() },
+            ctx.accounts.vault_token_account.to_account_info(),
+            ctx.accounts.recipient_token_account.to_account_info(),
+            ctx.accounts.fee_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            ctx.accounts.token_mint.decimals,
+            gross_amount,
+            fee_bps,
             signer_seeds,
-        );
-        token_interface::transfer_checked(cpi_ctx, transfer_amount, ctx.accounts.token_mint.decimals)?;
+        )?;
 
-        if fee_amount > 0 {
-            let fee_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                TransferChecked { from: ctx.accounts.vault_token_account.to_account_info(), to: ctx.accounts.fee_token_account.to_account_info(), authority: ctx.accounts.vault.to_account_info(), mint: ctx.accounts.token_mint.to_account_info() },
-                signer_seeds,
-            );
-            token_interface::transfer_checked(fee_ctx, fee_amount, ctx.accounts.token_mint.decimals)?;
+        match ctx.accounts.beneficiary_account.as_deref_mut() {
+            Some(account) => {
+                account.beneficiary.executed = true;
+                ctx.accounts.snapshot.record_account_paid(beneficiary.token_mint, beneficiary.amount)?;
+            }
+            None => ctx.accounts.ghost.beneficiaries[beneficiary_index as usize].executed = true,
         }
+        ctx.accounts.snapshot.record_paid(beneficiary.token_mint, gross_amount)?;
+        emit!(TransferExecuted { soul: owner, recipient: beneficiary.recipient, amount: transfer_amount });
+        msg!("Transferred {} to {} (fee: {})", transfer_amount, beneficiary.recipient, fee_amount);
+        Ok(())
+    }
+
+    // v1.11: pull-based payout — the beneficiary signs and pays for their own ATA, so a slot
+    // can be claimed even if no keeper is running. Same snapshot entitlement and fee as
+    // execute_transfer; the destination is always the signer's canonical ATA.
+    pub fn claim_beneficiary(ctx: Context<ClaimBeneficiary>, beneficiary_index: u8) -> Result<()> {
+        require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
+        require!((beneficiary_index as usize) < ctx.accounts.ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+        require!(!ctx.accounts.ghost.is_vesting_slot(beneficiary_index as usize), GhostError::VestingSlot);
+        require!(!ctx.accounts.ghost.is_disclaimed_slot(beneficiary_index as usize), GhostError::SlotDisclaimed);
+        let beneficiary = ctx.accounts.ghost.beneficiaries[beneficiary_index as usize];
+        require!(!beneficiary.executed, GhostError::BeneficiaryAlreadyPaid);
+        require!(beneficiary.action == 0, GhostError::NotATransferBeneficiary);
+        require!(beneficiary.recipient == ctx.accounts.beneficiary.key(), GhostError::WrongRecipient);
+        require!(Some(ctx.accounts.token_mint.key()) == beneficiary.token_mint, GhostError::WrongMint);
+        let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
+        let vault_bump = ctx.accounts.ghost.vault_bump;
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let gross_amount = ctx.accounts.snapshot.entitlement(beneficiary.token_mint, beneficiary.amount)?;
+
+        // Protocol fee at the ghost's locked-in rate
+        let fee_bps = ctx.accounts.ghost.fee_bps(&ctx.accounts.config);
+        let (transfer_amount, fee_amount) = transfer_with_fee(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault_token_account.to_account_info(),
+            ctx.accounts.beneficiary_token_account.to_account_info(),
+            ctx.accounts.fee_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            ctx.accounts.token_mint.decimals,
+            gross_amount,
+            fee_bps,
+            signer_seeds,
+        )?;
 
         ctx.accounts.ghost.beneficiaries[beneficiary_index as usize].executed = true;
+        ctx.accounts.snapshot.record_paid(beneficiary.token_mint, gross_amount)?;
         emit!(TransferExecuted { soul: owner, recipient: beneficiary.recipient, amount: transfer_amount });
-        msg!("Transferred {} to {} (fee: {})", transfer_amount, beneficiary.recipient, fee_amount);
+        msg!("Beneficiary {} claimed {} (fee: {})", beneficiary.recipient, transfer_amount, fee_amount);
         Ok(())
     }
 
-    pub fn execute_burn(ctx: Context<ExecuteBurn>, beneficiary_index: u8) -> Result<()> {
+    // v1.11: pays the newly unlocked part of a vesting slot. Permissionless — the
+    // destination is pinned to the recipient exactly as in execute_transfer.
+    pub fn claim_vested(ctx: Context<ClaimVested>, beneficiary_index: u8) -> Result<()> {
+        require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
+        require!((beneficiary_index as usize) < ctx.accounts.ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+        require!(ctx.accounts.ghost.is_vesting_slot(beneficiary_index as usize), GhostError::NotAVestingSlot);
+        let beneficiary = ctx.accounts.ghost.beneficiaries[beneficiary_index as usize];
+        let ext = ctx.accounts.ghost.beneficiary_ext[beneficiary_index as usize];
+        require!(!beneficiary.executed, GhostError::BeneficiaryAlreadyPaid);
+        require!(beneficiary.recipient == ctx.accounts.recipient.key(), GhostError::WrongRecipient);
+        require!(Some(ctx.accounts.token_mint.key()) == beneficiary.token_mint, GhostError::WrongMint);
+        let clock = Clock::get()?;
+        let executed_at = ctx.accounts.ghost.executed_at.ok_or(GhostError::GhostNotExecuted)?;
+
+        let entitlement = ctx.accounts.snapshot.entitlement(beneficiary.token_mint, beneficiary.amount)?;
+        let vested = ext.vested(entitlement, clock.unix_timestamp - executed_at);
+        let gross_amount = vested.saturating_sub(ext.claimed);
+        require!(gross_amount > 0, GhostError::NothingVested);
+
+        let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
+        let vault_bump = ctx.accounts.ghost.vault_bump;
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        // Protocol fee at the ghost's locked-in rate
+        let fee_bps = ctx.accounts.ghost.fee_bps(&ctx.accounts.config);
+        let (transfer_amount, fee_amount) = transfer_with_fee(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault_token_account.to_account_info(),
+            ctx.accounts.recipient_token_account.to_account_info(),
+            ctx.accounts.fee_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            ctx.accounts.token_mint.decimals,
+            gross_amount,
+            fee_bps,
+            signer_seeds,
+        )?;
+
+        let ghost = &mut ctx.accounts.ghost;
+        let claimed = ext.claimed + gross_amount;
+        ghost.beneficiary_ext[beneficiary_index as usize].claimed = claimed;
+        let fully_vested = claimed >= entitlement;
+        if fully_vested {
+            ghost.beneficiaries[beneficiary_index as usize].executed = true;
+        }
+        ctx.accounts.snapshot.record_paid(beneficiary.token_mint, gross_amount)?;
+        emit!(VestedClaimed { soul: owner, index: beneficiary_index, recipient: beneficiary.recipient, amount: transfer_amount, claimed, entitlement });
+        msg!("Released {} of {} to {} (fee: {}){}", claimed, entitlement, beneficiary.recipient, fee_amount, if fully_vested { " — fully vested" } else { "" });
+        Ok(())
+    }
+
+    // v1.11: pays a contingent slot's fallback recipient once the primary's claim window
+    // has passed or the primary has disclaimed. Permissionless; same entitlement and fee as
+    // execute_transfer.
+    pub fn execute_contingent_transfer(ctx: Context<ExecuteContingentTransfer>, beneficiary_index: u8) -> Result<()> {
         require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
         require!((beneficiary_index as usize) < ctx.accounts.ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+        require!(ctx.accounts.ghost.is_contingent_slot(beneficiary_index as usize), GhostError::InvalidContingentRecipient);
         let beneficiary = ctx.accounts.ghost.beneficiaries[beneficiary_index as usize];
+        let ext = ctx.accounts.ghost.beneficiary_ext[beneficiary_index as usize];
+        require!(!beneficiary.executed, GhostError::BeneficiaryAlreadyPaid);
+        require!(ext.contingent_recipient == Some(ctx.accounts.contingent_recipient.key()), GhostError::WrongRecipient);
+        require!(Some(ctx.accounts.token_mint.key()) == beneficiary.token_mint, GhostError::WrongMint);
+        let clock = Clock::get()?;
+        let executed_at = ctx.accounts.ghost.executed_at.ok_or(GhostError::GhostNotExecuted)?;
+        let contingent_at = executed_at.checked_add(ext.contingent_after_seconds).ok_or(GhostError::Overflow)?;
+        require!(ext.disclaimed || clock.unix_timestamp >= contingent_at, GhostError::ContingentWindowOpen);
+        let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
+        let vault_bump = ctx.accounts.ghost.vault_bump;
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let gross_amount = ctx.accounts.snapshot.entitlement(beneficiary.token_mint, beneficiary.amount)?;
+
+        // Protocol fee at the ghost's locked-in rate
+        let fee_bps = ctx.accounts.ghost.fee_bps(&ctx.accounts.config);
+        let (transfer_amount, fee_amount) = transfer_with_fee(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault_token_account.to_account_info(),
+            ctx.accounts.contingent_token_account.to_account_info(),
+            ctx.accounts.fee_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            ctx.accounts.token_mint.decimals,
+            gross_amount,
+            fee_bps,
+            signer_seeds,
+        )?;
+
+        ctx.accounts.ghost.beneficiaries[beneficiary_index as usize].executed = true;
+        ctx.accounts.snapshot.record_paid(beneficiary.token_mint, gross_amount)?;
+        let contingent = ctx.accounts.contingent_recipient.key();
+        emit!(ContingentTransferExecuted { soul: owner, index: beneficiary_index, primary: beneficiary.recipient, contingent, amount: transfer_amount });
+        msg!("Slot {} unclaimed by {} — transferred {} to contingent {} (fee: {})", beneficiary_index, beneficiary.recipient, transfer_amount, contingent, fee_amount);
+        Ok(())
+    }
+
+    // v1.11: the recipient renounces their slot. Nothing moves here — the slot is either
+    // handed to its contingent recipient, folded into the other slots of its mint, or
+    // marked executed so the estate sweeps pick up its amount.
+    pub fn disclaim_beneficiary(ctx: Context<DisclaimBeneficiary>, beneficiary_index: u8) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.executed, GhostError::GhostNotExecuted);
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        let index = beneficiary_index as usize;
+        require!(index < ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+        let beneficiary = ghost.beneficiaries[index];
+        require!(!beneficiary.executed, GhostError::BeneficiaryAlreadyPaid);
+        require!(beneficiary.action == 0, GhostError::NotATransferBeneficiary);
+        require!(beneficiary.recipient == ctx.accounts.beneficiary.key(), GhostError::WrongRecipient);
+        require!(!ghost.beneficiary_ext[index].disclaimed, GhostError::SlotDisclaimed);
+        // Taking any part of a vesting slot is acceptance — the rest can't be disclaimed
+        require!(ghost.beneficiary_ext[index].claimed == 0, GhostError::BeneficiaryAlreadyPaid);
+
+        ghost.beneficiary_ext[index].disclaimed = true;
+        let routed_to = if ghost.beneficiary_ext[index].has_contingent() {
+            DISCLAIM_ROUTED_CONTINGENT
+        } else {
+            let shared = ghost.disclaim_policy == DISCLAIM_POLICY_PRO_RATA && ghost.redistribute_slot(index)?;
+            ghost.beneficiaries[index].executed = true;
+            if shared { DISCLAIM_POLICY_PRO_RATA } else { DISCLAIM_POLICY_ESTATE }
+        };
+        emit!(BeneficiaryDisclaimed { soul: ghost.owner, index: beneficiary_index, recipient: beneficiary.recipient, mint: beneficiary.token_mint, amount: beneficiary.amount, routed_to });
+        msg!("Beneficiary {} disclaimed slot {} (routed to {})", beneficiary.recipient, beneficiary_index, routed_to);
+        Ok(())
+    }
+
+    // v1.11: same embedded-slot / beneficiary_account choice as execute_transfer
+    pub fn execute_burn(ctx: Context<ExecuteBurn>, beneficiary_index: u8) -> Result<()> {
+        require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
+        let beneficiary = match ctx.accounts.beneficiary_account.as_deref() {
+            Some(account) => account.tallied_slot()?,
+            None => {
+                require!((beneficiary_index as usize) < ctx.accounts.ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+                ctx.accounts.ghost.beneficiaries[beneficiary_index as usize]
+            }
+        };
         require!(!beneficiary.executed, GhostError::BeneficiaryAlreadyPaid);
         require!(beneficiary.action == 1, GhostError::NotABurnBeneficiary);
         require!(Some(ctx.accounts.mint.key()) == beneficiary.token_mint, GhostError::WrongMint);
         let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
         let vault_bump = ctx.accounts.ghost.vault_bump;
-        let seeds = &[VAULT_SEED, owner.as_ref(), &[vault_bump]];
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
         let signer_seeds = &[&seeds[..]];
+        // v1.11: burns are pro-rated against the snapshot exactly like transfers
+        let burn_amount = ctx.accounts.snapshot.entitlement(beneficiary.token_mint, beneficiary.amount)?;
         let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             Burn { mint: ctx.accounts.mint.to_account_info(), from: ctx.accounts.vault_token_account.to_account_info(), authority: ctx.accounts.vault.to_account_info() },
             signer_seeds,
         );
-        token_interface::burn(cpi_ctx, beneficiary.amount)?;
-        ctx.accounts.ghost.beneficiaries[beneficiary_index as usize].executed = true;
-        emit!(BurnExecuted { soul: owner, mint: ctx.accounts.mint.key(), amount: beneficiary.amount });
-        msg!("Burned {} tokens from vault", beneficiary.amount);
+        token_interface::burn(cpi_ctx, burn_amount)?;
+        match ctx.accounts.beneficiary_account.as_deref_mut() {
+            Some(account) => {
+                account.beneficiary.executed = true;
+                ctx.accounts.snapshot.record_account_paid(beneficiary.token_mint, beneficiary.amount)?;
+            }
+            None => ctx.accounts.ghost.beneficiaries[beneficiary_index as usize].executed = true,
+        }
+        ctx.accounts.snapshot.record_paid(beneficiary.token_mint, burn_amount)?;
+        emit!(BurnExecuted { soul: owner, mint: ctx.accounts.mint.key(), amount: burn_amount });
+        msg!("Burned {} tokens from vault", burn_amount);
         Ok(())
     }
 
@@ -562,46 +2169,131 @@ pub mod ghost_protocol {
         require!(ctx.accounts.ghost.whole_vault_action == 0, GhostError::NotATransferBeneficiary);
         require!(ctx.accounts.ghost.whole_vault_recipient.is_some(), GhostError::InvalidBeneficiary);
         require!(ctx.accounts.ghost.whole_vault_recipient.unwrap() == ctx.accounts.recipient.key(), GhostError::WrongRecipient);
-        let amount = ctx.accounts.vault_token_account.amount;
+        // v1.11: leave behind whatever unpaid beneficiary slots are still owed
+        let reserved = reserved_for_mint(&ctx.accounts.ghost, &ctx.accounts.snapshot, Some(ctx.accounts.token_mint.key()))?;
+        let amount = ctx.accounts.vault_token_account.amount.saturating_sub(reserved);
         require!(amount > 0, GhostError::Overflow);
         let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
         let vault_bump = ctx.accounts.ghost.vault_bump;
-        let seeds = &[VAULT_SEED, owner.as_ref(), &[vault_bump]];
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
         let signer_seeds = &[&seeds[..]];
 
-        // 0.5% protocol fee
-        let fee_amount = amount.checked_mul(EXECUTION_FEE_BPS).unwrap_or(0) / 10_000;
-        let transfer_amount = amount.saturating_sub(fee_amount);
-
-        let cpi_ctx = CpiContext::new_with_signer(
+        // Protocol fee at the ghost's locked-in rate
+        let fee_bps = ctx.accounts.ghost.fee_bps(&ctx.accounts.config);
+        let (transfer_amount, fee_amount) = transfer_with_fee(
             ctx.accounts.token_program.to_account_info(),
-            TransferChecked { from: ctx.accounts.vault_token_account.to_account_info(), to: ctx.accounts.recipient_token_account.to_account_info(), authority: ctx.accounts.vault.to_account_info(), mint: ctx.accounts.token_mint.to_account_info() },
+            ctx.accounts.vault_token_account.to_account_info(),
+            ctx.accounts.recipient_token_account.to_account_info(),
+            ctx.accounts.fee_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            ctx.accounts.token_mint.decimals,
+            amount,
+            fee_bps,
             signer_seeds,
-        );
-        token_interface::transfer_checked(cpi_ctx, transfer_amount, ctx.accounts.token_mint.decimals)?;
+        )?;
+
+        ctx.accounts.snapshot.record_sweep(ctx.accounts.token_mint.key(), amount, false)?;
+        emit!(TransferExecuted { soul: owner, recipient: ctx.accounts.recipient.key(), amount: transfer_amount });
+        msg!("Whole vault transfer: {} to {} (fee: {})", transfer_amount, ctx.accounts.recipient.key(), fee_amount);
+        Ok(())
+    }
 
-        if fee_amount > 0 {
-            let fee_ctx = CpiContext::new_with_signer(
+    // v1.11: execute_whole_vault_transfer for many mints at once. remaining_accounts holds
+    // groups of [vault_token_account, mint, recipient_token_account, fee_token_account], all
+    // under `token_program`. A vault token account with nothing reserved for unpaid slots is
+    // left empty and closed, with its rent going to the recipient.
+    pub fn execute_whole_vault_sweep<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteWholeVaultSweep<'info>>) -> Result<()> {
+        require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
+        require!(ctx.accounts.ghost.whole_vault_action == 0, GhostError::NotATransferBeneficiary);
+        require!(ctx.accounts.ghost.whole_vault_recipient.is_some(), GhostError::InvalidBeneficiary);
+        require!(ctx.accounts.ghost.whole_vault_recipient.unwrap() == ctx.accounts.recipient.key(), GhostError::WrongRecipient);
+        let groups = ctx.remaining_accounts;
+        require!(!groups.is_empty() && groups.len().is_multiple_of(4), GhostError::InvalidSweepAccounts);
+        let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
+        let vault_bump = ctx.accounts.ghost.vault_bump;
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+        let vault_key = ctx.accounts.vault.key();
+        let recipient_key = ctx.accounts.recipient.key();
+        let token_program_key = ctx.accounts.token_program.key();
+        let mut mint_count: u8 = 0;
+        let mut closed_count: u8 = 0;
+
+        for group in groups.chunks(4) {
+            let [vault_token_account, token_mint, recipient_token_account, fee_token_account] = group else {
+                return err!(GhostError::InvalidSweepAccounts);
+            };
+            require!(token_mint.owner == &token_program_key, GhostError::InvalidSweepAccounts);
+            let decimals = {
+                let data = token_mint.try_borrow_data()?;
+                Mint::try_deserialize(&mut &data[..])?.decimals
+            };
+            let mint = token_mint.key();
+            let balance = read_token_account(vault_token_account, &token_program_key, vault_key, mint)?.amount;
+            read_token_account(recipient_token_account, &token_program_key, recipient_key, mint)?;
+            read_token_account(fee_token_account, &token_program_key, ctx.accounts.config.fee_wallet, mint)?;
+
+            // Leave behind whatever unpaid beneficiary slots are still owed
+            let reserved = reserved_for_mint(&ctx.accounts.ghost, &ctx.accounts.snapshot, Some(mint))?;
+            let amount = balance.saturating_sub(reserved);
+
+            // Protocol fee at the ghost's locked-in rate
+            let fee_bps = ctx.accounts.ghost.fee_bps(&ctx.accounts.config);
+            let (transfer_amount, fee_amount) = transfer_with_fee(
                 ctx.accounts.token_program.to_account_info(),
-                TransferChecked { from: ctx.accounts.vault_token_account.to_account_info(), to: ctx.accounts.fee_token_account.to_account_info(), authority: ctx.accounts.vault.to_account_info(), mint: ctx.accounts.token_mint.to_account_info() },
+                vault_token_account.clone(),
+                recipient_token_account.clone(),
+                fee_token_account.clone(),
+                ctx.accounts.vault.to_account_info(),
+                token_mint.clone(),
+                decimals,
+                amount,
+                fee_bps,
                 signer_seeds,
-            );
-            token_interface::transfer_checked(fee_ctx, fee_amount, ctx.accounts.token_mint.decimals)?;
+            )?;
+
+            // Nothing reserved → the whole balance just left, so the account is empty
+            let closed = reserved == 0;
+            if closed {
+                let close_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    CloseAccount { account: vault_token_account.clone(), destination: ctx.accounts.recipient.to_account_info(), authority: ctx.accounts.vault.to_account_info() },
+                    signer_seeds,
+                );
+                token_interface::close_account(close_ctx)?;
+                closed_count += 1;
+            }
+
+            ctx.accounts.snapshot.record_sweep(mint, amount, closed)?;
+            if transfer_amount > 0 {
+                emit!(TransferExecuted { soul: owner, recipient: recipient_key, amount: transfer_amount });
+                msg!("Whole vault sweep: {} of mint {} (fee: {})", transfer_amount, mint, fee_amount);
+            }
+            mint_count += 1;
         }
 
-        emit!(TransferExecuted { soul: owner, recipient: ctx.accounts.recipient.key(), amount: transfer_amount });
-        msg!("Whole vault transfer: {} to {} (fee: {})", transfer_amount, ctx.accounts.recipient.key(), fee_amount);
+        emit!(WholeVaultSwept { soul: owner, recipient: recipient_key, mint_count, closed_count });
+        msg!("Whole vault sweep: {} mints to {} ({} vault token accounts closed)", mint_count, recipient_key, closed_count);
         Ok(())
     }
 
     pub fn execute_whole_vault_burn(ctx: Context<ExecuteWholeVaultBurn>) -> Result<()> {
         require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
         require!(ctx.accounts.ghost.whole_vault_action == 1, GhostError::NotABurnBeneficiary);
-        let amount = ctx.accounts.vault_token_account.amount;
+        // v1.11: leave behind whatever unpaid beneficiary slots are still owed
+        let reserved = reserved_for_mint(&ctx.accounts.ghost, &ctx.accounts.snapshot, Some(ctx.accounts.token_mint.key()))?;
+        let amount = ctx.accounts.vault_token_account.amount.saturating_sub(reserved);
         require!(amount > 0, GhostError::Overflow);
         let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
         let vault_bump = ctx.accounts.ghost.vault_bump;
-        let seeds = &[VAULT_SEED, owner.as_ref(), &[vault_bump]];
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
         let signer_seeds = &[&seeds[..]];
         let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
@@ -614,6 +2306,263 @@ pub mod ghost_protocol {
         Ok(())
     }
 
+    // v1.11: native SOL payout for a `token_mint = None` beneficiary slot. Lamports move
+    // out of the vault PDA via a system transfer signed with the vault seeds.
+    pub fn execute_sol_transfer(ctx: Context<ExecuteSolTransfer>, beneficiary_index: u8) -> Result<()> {
+        require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
+        require!((beneficiary_index as usize) < ctx.accounts.ghost.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+        let beneficiary = ctx.accounts.ghost.beneficiaries[beneficiary_index as usize];
+        require!(!beneficiary.executed, GhostError::BeneficiaryAlreadyPaid);
+        require!(beneficiary.action == 0, GhostError::NotATransferBeneficiary);
+        require!(beneficiary.token_mint.is_none(), GhostError::WrongMint);
+        require!(beneficiary.recipient == ctx.accounts.recipient.key(), GhostError::WrongRecipient);
+        // v1.11: slot entitlement from the execution snapshot (pro-rata if SOL is short)
+        let gross_amount = ctx.accounts.snapshot.entitlement(None, beneficiary.amount)?;
+        require!(vault_sol_available(&ctx.accounts.vault.to_account_info())? >= gross_amount, GhostError::InsufficientVaultBalance);
+        let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
+        let vault_bump = ctx.accounts.ghost.vault_bump;
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        // Protocol fee at the ghost's locked-in rate
+        let fee_bps = ctx.accounts.ghost.fee_bps(&ctx.accounts.config);
+        let (transfer_amount, fee_amount) = transfer_lamports_with_fee(ctx.accounts.system_program.to_account_info(), ctx.accounts.vault.to_account_info(), ctx.accounts.recipient.to_account_info(), ctx.accounts.fee_wallet.to_account_info(), gross_amount, fee_bps, signer_seeds)?;
+
+        ctx.accounts.ghost.beneficiaries[beneficiary_index as usize].executed = true;
+        ctx.accounts.snapshot.record_paid(None, gross_amount)?;
+        emit!(SolTransferExecuted { soul: owner, recipient: beneficiary.recipient, amount: transfer_amount });
+        msg!("Transferred {} lamports to {} (fee: {})", transfer_amount, beneficiary.recipient, fee_amount);
+        Ok(())
+    }
+
+    // v1.11: sweep every distributable lamport in the vault PDA to whole_vault_recipient.
+    // The rent-exempt reserve stays behind so the vault remains a valid system account.
+    pub fn execute_whole_vault_sol_transfer(ctx: Context<ExecuteWholeVaultSolTransfer>) -> Result<()> {
+        require!(ctx.accounts.ghost.executed, GhostError::GhostNotExecuted);
+        require!(ctx.accounts.ghost.whole_vault_action == 0, GhostError::NotATransferBeneficiary);
+        require!(ctx.accounts.ghost.whole_vault_recipient.is_some(), GhostError::InvalidBeneficiary);
+        require!(ctx.accounts.ghost.whole_vault_recipient.unwrap() == ctx.accounts.recipient.key(), GhostError::WrongRecipient);
+        // v1.11: leave behind whatever unpaid SOL slots are still owed
+        let reserved = reserved_for_mint(&ctx.accounts.ghost, &ctx.accounts.snapshot, None)?;
+        let amount = vault_sol_available(&ctx.accounts.vault.to_account_info())?.saturating_sub(reserved);
+        require!(amount > 0, GhostError::InsufficientVaultBalance);
+        let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
+        let vault_bump = ctx.accounts.ghost.vault_bump;
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        // Protocol fee at the ghost's locked-in rate
+        let fee_bps = ctx.accounts.ghost.fee_bps(&ctx.accounts.config);
+        let (transfer_amount, fee_amount) = transfer_lamports_with_fee(ctx.accounts.system_program.to_account_info(), ctx.accounts.vault.to_account_info(), ctx.accounts.recipient.to_account_info(), ctx.accounts.fee_wallet.to_account_info(), amount, fee_bps, signer_seeds)?;
+
+        emit!(SolTransferExecuted { soul: owner, recipient: ctx.accounts.recipient.key(), amount: transfer_amount });
+        msg!("Whole vault SOL transfer: {} lamports to {} (fee: {})", transfer_amount, ctx.accounts.recipient.key(), fee_amount);
+        Ok(())
+    }
+
+    // v1.11: sweep the leftover balance of one mint to residual_recipient. Only once every
+    // slot is executed, so nothing is owed to a beneficiary, and only when no
+    // whole_vault_recipient is set — that flow already claims the leftovers.
+    pub fn execute_residual_sweep(ctx: Context<ExecuteResidualSweep>) -> Result<()> {
+        let ghost = &ctx.accounts.ghost;
+        require!(ghost.executed, GhostError::GhostNotExecuted);
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(ghost.whole_vault_recipient.is_none(), GhostError::WholeVaultRecipientActive);
+        require!(beneficiaries_settled(ghost, &ctx.accounts.snapshot), GhostError::BeneficiariesOutstanding);
+        require!(ghost.residual_recipient == Some(ctx.accounts.recipient.key()), GhostError::WrongRecipient);
+        let amount = ctx.accounts.vault_token_account.amount;
+        require!(amount > 0, GhostError::Overflow);
+        let owner = ghost.owner;
+        let seed_owner = *ghost.seed_owner();
+        let index_seed = ghost.index_seed();
+        let vault_bump = ghost.vault_bump;
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        // Protocol fee at the ghost's locked-in rate
+        let fee_bps = ctx.accounts.ghost.fee_bps(&ctx.accounts.config);
+        let (transfer_amount, fee_amount) = transfer_with_fee(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault_token_account.to_account_info(),
+            ctx.accounts.recipient_token_account.to_account_info(),
+            ctx.accounts.fee_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            ctx.accounts.token_mint.decimals,
+            amount,
+            fee_bps,
+            signer_seeds,
+        )?;
+
+        // Recorded so finalize_ghost waits for this vault token account to be closed
+        ctx.accounts.snapshot.record_sweep(ctx.accounts.token_mint.key(), amount, false)?;
+        emit!(ResidualSwept { soul: owner, recipient: ctx.accounts.recipient.key(), mint: Some(ctx.accounts.token_mint.key()), amount: transfer_amount });
+        msg!("Residual sweep: {} of mint {} to {} (fee: {})", transfer_amount, ctx.accounts.token_mint.key(), ctx.accounts.recipient.key(), fee_amount);
+        Ok(())
+    }
+
+    // v1.11: native SOL counterpart of execute_residual_sweep (rent reserve stays behind).
+    pub fn execute_residual_sol_sweep(ctx: Context<ExecuteResidualSolSweep>) -> Result<()> {
+        let ghost = &ctx.accounts.ghost;
+        require!(ghost.executed, GhostError::GhostNotExecuted);
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(ghost.whole_vault_recipient.is_none(), GhostError::WholeVaultRecipientActive);
+        require!(beneficiaries_settled(ghost, &ctx.accounts.snapshot), GhostError::BeneficiariesOutstanding);
+        require!(ghost.residual_recipient == Some(ctx.accounts.recipient.key()), GhostError::WrongRecipient);
+        let amount = vault_sol_available(&ctx.accounts.vault.to_account_info())?;
+        require!(amount > 0, GhostError::InsufficientVaultBalance);
+        let owner = ghost.owner;
+        let seed_owner = *ghost.seed_owner();
+        let index_seed = ghost.index_seed();
+        let vault_bump = ghost.vault_bump;
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        // Protocol fee at the ghost's locked-in rate
+        let fee_bps = ctx.accounts.ghost.fee_bps(&ctx.accounts.config);
+        let (transfer_amount, fee_amount) = transfer_lamports_with_fee(ctx.accounts.system_program.to_account_info(), ctx.accounts.vault.to_account_info(), ctx.accounts.recipient.to_account_info(), ctx.accounts.fee_wallet.to_account_info(), amount, fee_bps, signer_seeds)?;
+
+        emit!(ResidualSwept { soul: owner, recipient: ctx.accounts.recipient.key(), mint: None, amount: transfer_amount });
+        msg!("Residual SOL sweep: {} lamports to {} (fee: {})", transfer_amount, ctx.accounts.recipient.key(), fee_amount);
+        Ok(())
+    }
+
+    // v1.11: closes one empty vault token account once nothing more can be owed from it,
+    // recording the closure in the snapshot so finalize_ghost can check every mint is done.
+    pub fn close_vault_token_account(ctx: Context<CloseVaultTokenAccount>) -> Result<()> {
+        let ghost = &ctx.accounts.ghost;
+        require!(ghost.executed, GhostError::GhostNotExecuted);
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(beneficiaries_settled(ghost, &ctx.accounts.snapshot), GhostError::BeneficiariesOutstanding);
+        require!(ctx.accounts.vault_token_account.amount == 0, GhostError::VaultNotEmpty);
+        let owner = ghost.owner;
+        let seed_owner = *ghost.seed_owner();
+        let index_seed = ghost.index_seed();
+        let vault_bump = ghost.vault_bump;
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount { account: ctx.accounts.vault_token_account.to_account_info(), destination: ctx.accounts.rent_recipient.to_account_info(), authority: ctx.accounts.vault.to_account_info() },
+            signer_seeds,
+        );
+        token_interface::close_account(close_ctx)?;
+        let mint = ctx.accounts.token_mint.key();
+        ctx.accounts.snapshot.record_sweep(mint, 0, true)?;
+        emit!(VaultTokenAccountClosed { soul: owner, mint, rent_recipient: ctx.accounts.rent_recipient.key() });
+        msg!("Vault token account for mint {} closed", mint);
+        Ok(())
+    }
+
+    // v1.11: closes a paid BeneficiaryAccount after execution. Its rent was the owner's, so it
+    // goes to the rent recipient rather than the caller.
+    pub fn close_beneficiary_account(ctx: Context<CloseBeneficiaryAccount>) -> Result<()> {
+        let ghost = &mut ctx.accounts.ghost;
+        require!(ghost.executed, GhostError::GhostNotExecuted);
+        require!(ctx.accounts.beneficiary_account.tallied_slot()?.executed, GhostError::BeneficiariesOutstanding);
+        ghost.beneficiary_accounts = ghost.beneficiary_accounts.saturating_sub(1);
+        emit!(BeneficiaryAccountRemoved { soul: ghost.owner, id: ctx.accounts.beneficiary_account.id });
+        msg!("Beneficiary account #{} closed", ctx.accounts.beneficiary_account.id);
+        Ok(())
+    }
+
+    // v1.11: permissionless teardown of a fully distributed ghost. remaining_accounts =
+    // [vault_token_account, token_program] pairs for the vault token accounts still open;
+    // each must be empty and is closed here. The leftover stake, the vault's lamport reserve
+    // and the rent of every closed account all go to ghost.rent_destination().
+    pub fn finalize_ghost<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeGhost<'info>>) -> Result<()> {
+        let ghost = &ctx.accounts.ghost;
+        require!(ghost.executed, GhostError::GhostNotExecuted);
+        require!(ghost.is_v111(), GhostError::MigrationRequired);
+        require!(beneficiaries_settled(ghost, &ctx.accounts.snapshot), GhostError::BeneficiariesOutstanding);
+        require!(ghost.beneficiary_account_count() == 0, GhostError::BeneficiaryAccountsOpen);
+        require!(vault_sol_available(&ctx.accounts.vault.to_account_info())? == 0, GhostError::VaultNotEmpty);
+        let owner = ghost.owner;
+        let seed_owner = *ghost.seed_owner();
+        let index_seed = ghost.index_seed();
+        let bump = ghost.bump;
+        let vault_bump = ghost.vault_bump;
+        let rent_recipient = ctx.accounts.rent_recipient.key();
+        let vault_key = ctx.accounts.vault.key();
+
+        // ── Empty vault token accounts → closed, rent to the rent recipient ─────
+        let pairs = ctx.remaining_accounts;
+        require!(pairs.len().is_multiple_of(2), GhostError::InvalidFinalizeAccounts);
+        let vault_seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let vault_signer = &[&vault_seeds[..]];
+        for pair in pairs.chunks(2) {
+            let [vault_token_account, token_program] = pair else {
+                return err!(GhostError::InvalidFinalizeAccounts);
+            };
+            let account = read_vault_token_account(vault_token_account, vault_key).ok_or(GhostError::InvalidFinalizeAccounts)?;
+            require!(token_program.key == vault_token_account.owner, GhostError::InvalidFinalizeAccounts);
+            require!(account.amount == 0, GhostError::VaultNotEmpty);
+            let close_ctx = CpiContext::new_with_signer(
+                token_program.clone(),
+                CloseAccount { account: vault_token_account.clone(), destination: ctx.accounts.rent_recipient.to_account_info(), authority: ctx.accounts.vault.to_account_info() },
+                vault_signer,
+            );
+            token_interface::close_account(close_ctx)?;
+            ctx.accounts.snapshot.record_sweep(account.mint, 0, true)?;
+            emit!(VaultTokenAccountClosed { soul: owner, mint: account.mint, rent_recipient });
+        }
+        require!(ctx.accounts.snapshot.vault_token_accounts_closed(), GhostError::VaultNotEmpty);
+
+        // ── Leftover stake → rent recipient, then close the stake vault ─────────
+        // SOL-deposit ghosts have no stake vault: the rest of the deposit is in the ghost
+        // PDA's lamports and leaves with its close.
+        let mut stake_returned = 0;
+        if !ctx.accounts.ghost.is_sol_staked() {
+            let (Some(ghost_mint), Some(ghost_stake_vault), Some(rent_recipient_token_account), Some(token_program)) = (
+                ctx.accounts.ghost_mint.as_ref(),
+                ctx.accounts.ghost_stake_vault.as_ref(),
+                ctx.accounts.rent_recipient_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ) else {
+                return err!(GhostError::MissingStakeAccounts);
+            };
+            let ghost_seeds = &[GHOST_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[bump]];
+            let ghost_signer = &[&ghost_seeds[..]];
+            stake_returned = ghost_stake_vault.amount;
+            if stake_returned > 0 {
+                let return_ctx = CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    TransferChecked { from: ghost_stake_vault.to_account_info(), to: rent_recipient_token_account.to_account_info(), authority: ctx.accounts.ghost.to_account_info(), mint: ghost_mint.to_account_info() },
+                    ghost_signer,
+                );
+                token_interface::transfer_checked(return_ctx, stake_returned, ghost_mint.decimals)?;
+            }
+            let close_ctx = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                CloseAccount { account: ghost_stake_vault.to_account_info(), destination: ctx.accounts.rent_recipient.to_account_info(), authority: ctx.accounts.ghost.to_account_info() },
+                ghost_signer,
+            );
+            token_interface::close_account(close_ctx)?;
+        }
+
+        // ── Vault rent reserve — emptying a system account entirely is allowed ──
+        let reserve = ctx.accounts.vault.lamports();
+        if reserve > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer { from: ctx.accounts.vault.to_account_info(), to: ctx.accounts.rent_recipient.to_account_info() },
+                    vault_signer,
+                ),
+                reserve,
+            )?;
+        }
+
+        // snapshot and ghost are closed to rent_recipient by their account constraints
+        emit!(GhostFinalized { soul: owner, rent_recipient, stake_returned });
+        msg!("Ghost finalized — stake {} and rent returned to {}", stake_returned, rent_recipient);
+        Ok(())
+    }
+
     pub fn deposit_to_vault(ctx: Context<DepositToVault>, amount: u64) -> Result<()> {
         let cpi_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -628,8 +2577,10 @@ pub mod ghost_protocol {
     pub fn withdraw_from_vault(ctx: Context<WithdrawFromVault>, amount: u64) -> Result<()> {
         require!(!ctx.accounts.ghost.awakened, GhostError::GhostAlreadyAwakened);
         let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
         let vault_bump = ctx.accounts.ghost.vault_bump;
-        let seeds = &[VAULT_SEED, owner.as_ref(), &[vault_bump]];
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
         let signer_seeds = &[&seeds[..]];
         let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
@@ -642,13 +2593,54 @@ pub mod ghost_protocol {
         Ok(())
     }
 
+    // v1.11: native SOL deposit — plain system transfer from the owner into the vault PDA.
+    // The first deposit must cover the vault's rent-exempt reserve (~0.00089 SOL).
+    pub fn deposit_sol_to_vault(ctx: Context<DepositSolToVault>, amount: u64) -> Result<()> {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer { from: ctx.accounts.signer.to_account_info(), to: ctx.accounts.vault.to_account_info() },
+            ),
+            amount,
+        )?;
+        emit!(SolVaultDeposit { soul: ctx.accounts.ghost.owner, amount });
+        msg!("Deposited {} lamports to vault", amount);
+        Ok(())
+    }
+
+    pub fn withdraw_sol_from_vault(ctx: Context<WithdrawSolFromVault>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(vault_sol_available(&ctx.accounts.vault.to_account_info())? >= amount, GhostError::InsufficientVaultBalance);
+        let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        let index_seed = ctx.accounts.ghost.index_seed();
+        let vault_bump = ctx.accounts.ghost.vault_bump;
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer { from: ctx.accounts.vault.to_account_info(), to: ctx.accounts.signer.to_account_info() },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        emit!(SolVaultWithdrawal { soul: owner, amount });
+        msg!("Withdrew {} lamports from vault", amount);
+        Ok(())
+    }
+
     pub fn recovery_withdraw(ctx: Context<RecoveryWithdraw>, amount: u64) -> Result<()> {
         let ghost = &ctx.accounts.ghost;
         require!(is_recovery_wallet(&ghost.recovery_wallets, ctx.accounts.recovery_wallet.key()), GhostError::Unauthorized);
+        // v1.11: with a quorum or time-lock this must be a GuardianAction::RecoveryWithdraw proposal
+        require!(ghost.guardian_actions_direct(), GhostError::GuardianProposalRequired);
         require!(!ghost.executed, GhostError::GhostAlreadyExecuted);
         let owner = ghost.owner;
+        let seed_owner = *ghost.seed_owner();
+        let index_seed = ghost.index_seed();
         let vault_bump = ghost.vault_bump;
-        let seeds = &[VAULT_SEED, owner.as_ref(), &[vault_bump]];
+        let seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
         let signer_seeds = &[&seeds[..]];
         let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
@@ -661,29 +2653,121 @@ pub mod ghost_protocol {
         Ok(())
     }
 
-    pub fn abandon_ghost(ctx: Context<AbandonGhost>) -> Result<()> {
+    // v1.11: only for a ghost still in the owner's hands. The vault is emptied to the owner
+    // first — once the GhostAccount is gone nothing can sign for it. remaining_accounts:
+    // groups of [vault_token_account, mint, owner_token_account, token_program].
+    pub fn abandon_ghost<'info>(ctx: Context<'_, '_, '_, 'info, AbandonGhost<'info>>) -> Result<()> {
+        require!(!ctx.accounts.ghost.awakened, GhostError::GhostAlreadyAwakened);
+        require!(!ctx.accounts.ghost.executed, GhostError::GhostAlreadyExecuted);
+        require!(ctx.accounts.ghost.beneficiary_account_count() == 0, GhostError::BeneficiaryAccountsOpen);
         let owner = ctx.accounts.ghost.owner;
+        let seed_owner = *ctx.accounts.ghost.seed_owner();
+        // A vault token account left out of the groups is reachable only by re-registering
+        // this ghost address, which needs the key it was derived from.
+        require!(seed_owner == owner, GhostError::OwnershipTransferred);
+        let index_seed = ctx.accounts.ghost.index_seed();
         let bump = ctx.accounts.ghost.bump;
+        let vault_bump = ctx.accounts.ghost.vault_bump;
+        let vault_key = ctx.accounts.vault.key();
+        let signer_key = ctx.accounts.signer.key();
+
+        // ── Full vault withdrawal to the owner ──────────────────────────────
+        let vault_seeds = &[VAULT_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[vault_bump]];
+        let vault_signer = &[&vault_seeds[..]];
+        let groups = ctx.remaining_accounts;
+        require!(groups.len().is_multiple_of(4), GhostError::InvalidWithdrawAccounts);
+        let mut token_accounts_closed: u8 = 0;
+        for group in groups.chunks(4) {
+            let [vault_token_account, token_mint, owner_vault_token_account, token_program] = group else {
+                return err!(GhostError::InvalidWithdrawAccounts);
+            };
+            require!(token_program.key() == anchor_spl::token::ID || token_program.key() == anchor_spl::token_2022::ID, GhostError::InvalidWithdrawAccounts);
+            require!(token_mint.owner == token_program.key, GhostError::InvalidWithdrawAccounts);
+            let decimals = {
+                let data = token_mint.try_borrow_data()?;
+                Mint::try_deserialize(&mut &data[..])?.decimals
+            };
+            let mint = token_mint.key();
+            let balance = read_token_account(vault_token_account, token_program.key, vault_key, mint)?.amount;
+            read_token_account(owner_vault_token_account, token_program.key, signer_key, mint)?;
+            if balance > 0 {
+                let cpi_ctx = CpiContext::new_with_signer(
+                    token_program.clone(),
+                    TransferChecked { from: vault_token_account.clone(), to: owner_vault_token_account.clone(), authority: ctx.accounts.vault.to_account_info(), mint: token_mint.clone() },
+                    vault_signer,
+                );
+                token_interface::transfer_checked(cpi_ctx, balance, decimals)?;
+            }
+            let close_ctx = CpiContext::new_with_signer(
+                token_program.clone(),
+                CloseAccount { account: vault_token_account.clone(), destination: ctx.accounts.signer.to_account_info(), authority: ctx.accounts.vault.to_account_info() },
+                vault_signer,
+            );
+            token_interface::close_account(close_ctx)?;
+            token_accounts_closed += 1;
+        }
+        // Every lamport, rent reserve included — emptying a system account entirely is allowed
+        let lamports = ctx.accounts.vault.lamports();
+        if lamports > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer { from: ctx.accounts.vault.to_account_info(), to: ctx.accounts.signer.to_account_info() },
+                    vault_signer,
+                ),
+                lamports,
+            )?;
+        }
+        emit!(AbandonVaultWithdrawn { soul: owner, token_accounts_closed, lamports });
+
+        // v1.11: a SOL deposit lives in the ghost PDA's lamports, so closing it to the signer
+        // is the full refund — nothing is burned.
+        if ctx.accounts.ghost.is_sol_staked() {
+            let refunded = ctx.accounts.ghost.sol_deposit;
+            emit!(SolDepositRefunded { soul: owner, amount: refunded });
+            emit!(GhostAbandoned { soul: owner, burned: 0, returned: 0, burn_bps: 0 });
+            msg!("Ghost abandoned. Refunded {} lamport deposit", refunded);
+            return Ok(());
+        }
+        let (Some(ghost_mint), Some(ghost_stake_vault), Some(owner_token_account), Some(token_program)) = (
+            ctx.accounts.ghost_mint.as_ref(),
+            ctx.accounts.ghost_stake_vault.as_ref(),
+            ctx.accounts.owner_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+        ) else {
+            return err!(GhostError::MissingStakeAccounts);
+        };
         // Use actual vault balance, not staked_ghost — bounties may have reduced it
-        let actual_balance = ctx.accounts.ghost_stake_vault.amount;
-        let burn_amount = actual_balance.checked_mul(BURN_ON_ABANDON_BPS).unwrap().checked_div(10_000).unwrap();
+        let actual_balance = ghost_stake_vault.amount;
+        // v1.11: nothing burned inside the trial window, then a linear ramp to BURN_ON_ABANDON_BPS
+        let burn_bps = ctx.accounts.ghost.abandon_burn_bps(&ctx.accounts.config, Clock::get()?.unix_timestamp);
+        let burn_amount = actual_balance.checked_mul(burn_bps).unwrap().checked_div(10_000).unwrap();
         let return_amount = actual_balance.checked_sub(burn_amount).unwrap();
-        let seeds = &[GHOST_SEED, owner.as_ref(), &[bump]];
+        let seeds = &[GHOST_SEED, seed_owner.as_ref(), index_seed.as_slice(), &[bump]];
         let signer_seeds = &[&seeds[..]];
         let burn_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Burn { mint: ctx.accounts.ghost_mint.to_account_info(), from: ctx.accounts.ghost_stake_vault.to_account_info(), authority: ctx.accounts.ghost.to_account_info() },
+            token_program.to_account_info(),
+            Burn { mint: ghost_mint.to_account_info(), from: ghost_stake_vault.to_account_info(), authority: ctx.accounts.ghost.to_account_info() },
             signer_seeds,
         );
-        token_interface::burn(burn_ctx, burn_amount)?;
+        if burn_amount > 0 {
+            token_interface::burn(burn_ctx, burn_amount)?;
+        }
         let return_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked { from: ctx.accounts.ghost_stake_vault.to_account_info(), to: ctx.accounts.owner_token_account.to_account_info(), authority: ctx.accounts.ghost.to_account_info(), mint: ctx.accounts.ghost_mint.to_account_info() },
+            token_program.to_account_info(),
+            TransferChecked { from: ghost_stake_vault.to_account_info(), to: owner_token_account.to_account_info(), authority: ctx.accounts.ghost.to_account_info(), mint: ghost_mint.to_account_info() },
+            signer_seeds,
+        );
+        token_interface::transfer_checked(return_ctx, return_amount, ghost_mint.decimals)?;
+        // v1.11: the stake vault is empty now — close it rather than leave its rent behind
+        let close_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            CloseAccount { account: ghost_stake_vault.to_account_info(), destination: ctx.accounts.signer.to_account_info(), authority: ctx.accounts.ghost.to_account_info() },
             signer_seeds,
         );
-        token_interface::transfer_checked(return_ctx, return_amount, ctx.accounts.ghost_mint.decimals)?;
-        emit!(GhostAbandoned { soul: owner, burned: burn_amount, returned: return_amount });
-        msg!("Ghost abandoned. Burned: {}, Returned: {}", burn_amount, return_amount);
+        token_interface::close_account(close_ctx)?;
+        emit!(GhostAbandoned { soul: owner, burned: burn_amount, returned: return_amount, burn_bps: burn_bps as u16 });
+        msg!("Ghost abandoned. Burned: {} ({} bps), Returned: {}", burn_amount, burn_bps, return_amount);
         Ok(())
     }
 
@@ -700,14 +2784,25 @@ pub mod ghost_protocol {
     ///   - Update CURRENT_SCHEMA_VERSION constant to SCHEMA_VERSION_V19.
     ///   - Do NOT change the realloc target — always use GHOST_ACCOUNT_SPACE.
     ///
-    /// Security: only callable by ghost.owner. Idempotent — safe to run multiple times.
+    /// Security: only callable by ghost.owner until the ghost has executed, then by anyone
+    /// (the caller pays any realloc rent). Idempotent — safe to run multiple times.
     /// All beneficiaries, heartbeat, stake, vault assets — all untouched.
     pub fn migrate_ghost(ctx: Context<MigrateGhost>) -> Result<()> {
         let ghost_info = ctx.accounts.ghost.to_account_info();
-        let owner = ctx.accounts.ghost.owner;
+        let signer = ctx.accounts.signer.key();
         let clock = Clock::get()?;
         let current_len = ghost_info.data_len();
 
+        // v1.11: the ghost arrives as a raw account so an old layout that no longer fits the
+        // current struct can be grown BEFORE it is deserialized. Authenticated by hand:
+        // program-owned (account constraint), GhostAccount discriminator, and — once
+        // deserialized below — owner field == signer unless the ghost has already executed.
+        // Only this program can write that discriminator, so no PDA re-derivation is needed.
+        {
+            let data = ghost_info.try_borrow_data()?;
+            require!(data.len() >= 8 + 32 && data[..8] == GhostAccount::DISCRIMINATOR[..], GhostError::InvalidAccountSize);
+        }
+
         // Realloc to current GHOST_ACCOUNT_SPACE if account is smaller.
         // GHOST_ACCOUNT_SPACE grows by the byte size of each new field added per version.
         // The +8 accounts for the Anchor discriminator prefix.
@@ -732,12 +2827,65 @@ pub mod ghost_protocol {
             ghost_info.realloc(target_len, false)?;
         }
 
+        let mut ghost = {
+            let data = ghost_info.try_borrow_data()?;
+            GhostAccount::try_deserialize(&mut &data[..])?
+        };
+        // v1.11: a ghost whose owner died before migrating would otherwise be stuck on the
+        // pre-v1.11 payout paths (no snapshot-based residual sweep, no finalize). Once executed,
+        // anyone may migrate it; the realloc above reverts with the instruction otherwise.
+        require!(ghost.owner == signer || ghost.executed, GhostError::Unauthorized);
+        let owner = ghost.owner;
+
         // Set new fields introduced in v1.8.
         // For each future version, append new field assignments here — do NOT remove old ones.
+        // v1.11 fields — defaulted only while schema_version is still below V111 (checked
+        // before the bump below), so re-running migrate_ghost never wipes configuration:
+        if ghost.schema_version < SCHEMA_VERSION_V111 {
+            ghost.beneficiary_ext = [BeneficiaryExt::default(); MAX_BENEFICIARIES];
+            // An executed ghost can no longer name one — its leftovers go back to the owner's
+            // wallet, the same fallback rent_destination() uses.
+            ghost.residual_recipient = if ghost.executed { Some(ghost.owner) } else { None };
+            ghost.guardian_threshold = 1;
+            ghost.proposal_count = 0;
+            ghost.guardian_delay_seconds = 0;
+            ghost.owner_ping_nonce = 0;
+            ghost.queued_guardian_actions = 0;
+            // Before v1.11 owner never changed, so it is the key the PDAs were derived from
+            ghost.original_owner = ghost.owner;
+            ghost.pending_owner = None;
+            ghost.pending_owner_since = 0;
+            ghost.ping_delegates = [PingDelegate::default(); MAX_PING_DELEGATES];
+            ghost.heartbeat_nonce = 0;
+            ghost.ghost_index = 0;
+            ghost.indexed = false;
+            ghost.beneficiary_accounts = 0;
+            ghost.next_beneficiary_id = 0;
+            ghost.disclaim_policy = DISCLAIM_POLICY_ESTATE;
+            ghost.rent_recipient = None;
+            // Registered under the v1.9 constant — that is the rate these owners agreed to
+            ghost.execution_fee_bps = EXECUTION_FEE_BPS as u16;
+            ghost.sol_staked = false;
+            ghost.sol_deposit = 0;
+            // An open-ended pre-v1.11 pause gets the default maximum, counted from now
+            ghost.paused_until = if ghost.paused { clock.unix_timestamp + MAX_PAUSE_SECONDS } else { 0 };
+            ghost.warning_bps = 0;
+            ghost.final_notice_bps = 0;
+            ghost.warned_at = 0;
+            ghost.final_notice_at = 0;
+            ghost.attested_at = 0;
+            ghost.attested_execute_at = 0;
+            ghost.account_mints = [AccountMint::default(); MAX_SNAPSHOT_MINTS];
+            ghost.stage_bounty_base = 0;
+        }
         // v1.8 fields:
-        ctx.accounts.ghost.schema_version = CURRENT_SCHEMA_VERSION;
-        // v1.9 fields would go here:
-        // ctx.accounts.ghost.your_new_field = default_value;
+        ghost.schema_version = CURRENT_SCHEMA_VERSION;
+
+        {
+            let mut data = ghost_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data[..];
+            ghost.try_serialize(&mut writer)?;
+        }
 
         emit!(MigrationComplete {
             soul: owner,
@@ -769,6 +2917,102 @@ impl Default for Beneficiary {
     }
 }
 
+// v1.11: per-slot extension data. Lives in a parallel array on GhostAccount (same index as
+// `beneficiaries`) so the Beneficiary byte layout — and every existing account — is untouched.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub struct BeneficiaryExt {
+    pub share_bps: u16,                          // 2 — 0 = absolute `amount`; >0 = bps of vault balance at execute_legacy
+    pub vesting_cliff_seconds: i64,              // 8 — after executed_at; nothing unlocks before
+    pub vesting_duration_seconds: i64,           // 8 — after the cliff; 0 with a cliff = all at the cliff
+    pub vesting_tranches: u16,                   // 2 — 0 = linear; n = n equal unlocks across the duration
+    pub claimed: u64,                            // 8 — gross amount already released by claim_vested
+    pub contingent_recipient: Option<Pubkey>,    // 33 — paid instead if the primary hasn't claimed in time
+    pub contingent_after_seconds: i64,           // 8 — after executed_at
+    pub disclaimed: bool,                        // 1 — renounced by the recipient via disclaim_beneficiary
+}
+
+impl BeneficiaryExt {
+    pub fn is_vesting(&self) -> bool {
+        self.vesting_cliff_seconds > 0 || self.vesting_duration_seconds > 0
+    }
+
+    pub fn has_contingent(&self) -> bool {
+        self.contingent_recipient.is_some()
+    }
+
+    // Portion of `entitlement` unlocked `elapsed` seconds after execution. Reaches exactly
+    // `entitlement` at cliff + duration.
+    pub fn vested(&self, entitlement: u64, elapsed: i64) -> u64 {
+        if !self.is_vesting() { return entitlement; }
+        if elapsed < self.vesting_cliff_seconds { return 0; }
+        let since_cliff = elapsed - self.vesting_cliff_seconds;
+        let duration = self.vesting_duration_seconds;
+        if since_cliff >= duration { return entitlement; }
+        let (num, den) = if self.vesting_tranches == 0 {
+            (since_cliff as u128, duration as u128)
+        } else {
+            let tranches = self.vesting_tranches as u128;
+            (since_cliff as u128 * tranches / duration as u128, tranches)
+        };
+        (entitlement as u128 * num / den) as u64
+    }
+}
+
+// v1.11: how many BeneficiaryAccounts pay out in one mint. count == 0 = free entry.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub struct AccountMint {
+    pub mint: Option<Pubkey>,                    // 33 — None = native SOL
+    pub count: u16,                              // 2
+}
+
+// v1.11: a key that may only call `ping`. key == Pubkey::default() marks an empty slot.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub struct PingDelegate {
+    pub key: Pubkey,                             // 32
+    pub expires_at: i64,                         // 8
+}
+
+impl PingDelegate {
+    pub fn is_live(&self, now: i64) -> bool {
+        self.key != Pubkey::default() && now < self.expires_at
+    }
+}
+
+// v1.11: a beneficiary slot stored outside GhostAccount. `tallied` is set once its amount
+// has been committed to the execution snapshot; it can only be paid after that.
+#[account]
+pub struct BeneficiaryAccount {
+    pub ghost: Pubkey,                           // 32
+    pub id: u32,                                 // 4
+    pub beneficiary: Beneficiary,                // BENEFICIARY_SIZE
+    pub ext: BeneficiaryExt,                     // BENEFICIARY_EXT_SIZE
+    pub tallied: bool,                           // 1
+    pub bump: u8,                                // 1
+}
+
+impl BeneficiaryAccount {
+    pub fn tallied_slot(&self) -> Result<Beneficiary> {
+        require!(self.tallied, GhostError::BeneficiaryNotTallied);
+        Ok(self.beneficiary)
+    }
+}
+
+// v1.11: protocol-wide fee settings. Singleton PDA ["config"].
+#[account]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,                           // 32
+    pub execution_fee_bps: u16,                  // 2 — ≤ MAX_EXECUTION_FEE_BPS
+    pub registration_fee_lamports: u64,          // 8 — ≤ MAX_REGISTRATION_FEE_LAMPORTS
+    pub min_stake: u64,                          // 8 — ≤ MAX_MIN_STAKE
+    pub fee_wallet: Pubkey,                      // 32
+    pub bot_ops_wallet: Pubkey,                  // 32
+    pub bump: u8,                                // 1
+    pub min_sol_deposit: u64,                    // 8 — initialize_sol_ghost, ≤ MAX_MIN_SOL_DEPOSIT_LAMPORTS
+    pub abandon_trial_seconds: i64,              // 8 — ≤ MAX_ABANDON_TRIAL_SECONDS
+    pub abandon_burn_ramp_seconds: i64,          // 8
+    pub max_pause_seconds: i64,                  // 8 — ≤ MAX_PAUSE_SECONDS_CAP
+}
+
 #[account]
 pub struct GhostAccount {
     pub owner: Pubkey,                           // 32
@@ -793,6 +3037,35 @@ pub struct GhostAccount {
     pub whole_vault_action: u8,                  // 1
     pub display_name: [u8; 32],                  // 32
     pub image_uri: [u8; 128],                    // 128
+    pub beneficiary_ext: [BeneficiaryExt; 10],   // 700 — v1.11
+    pub residual_recipient: Option<Pubkey>,      // 1 or 33 — v1.11
+    pub guardian_threshold: u8,                  // 1 — v1.11 (approvals needed per guardian action)
+    pub proposal_count: u64,                     // 8 — v1.11 (next GuardianProposal id)
+    pub guardian_delay_seconds: i64,             // 8 — v1.11 (owner veto window after quorum)
+    pub owner_ping_nonce: u64,                   // 8 — v1.11 (bumped by owner pings)
+    pub queued_guardian_actions: u8,             // 1 — v1.11
+    pub original_owner: Pubkey,                  // 32 — v1.11 (PDA seed; see seed_owner)
+    pub pending_owner_since: i64,                // 8 — v1.11 (transfer_ownership timestamp)
+    pub ping_delegates: [PingDelegate; 3],       // 120 — v1.11
+    pub heartbeat_nonce: u64,                    // 8 — v1.11 (next ping_with_signature nonce)
+    pub ghost_index: u16,                        // 2 — v1.11 (PDA seed when `indexed`)
+    pub indexed: bool,                           // 1 — v1.11 (false = legacy ["ghost", owner])
+    pub beneficiary_accounts: u32,               // 4 — v1.11 (live BeneficiaryAccount PDAs)
+    pub next_beneficiary_id: u32,                // 4 — v1.11
+    pub disclaim_policy: u8,                     // 1 — v1.11 (DISCLAIM_POLICY_*)
+    pub rent_recipient: Option<Pubkey>,          // 1 or 33 — v1.11 (see rent_destination)
+    pub execution_fee_bps: u16,                  // 2 — v1.11 (locked at registration; see fee_bps)
+    pub sol_staked: bool,                        // 1 — v1.11 (registered via initialize_sol_ghost)
+    pub sol_deposit: u64,                        // 8 — v1.11 (lamports held in this PDA above rent)
+    pub paused_until: i64,                       // 8 — v1.11 (end of the current or last pause)
+    pub warning_bps: u16,                        // 2 — v1.11 (0 = no warning stage)
+    pub final_notice_bps: u16,                   // 2 — v1.11 (0 = no final notice stage)
+    pub warned_at: i64,                          // 8 — v1.11 (last issue_warning)
+    pub final_notice_at: i64,                    // 8 — v1.11 (last issue_final_notice)
+    pub attested_at: i64,                        // 8 — v1.11 (last executed AttestDeath)
+    pub attested_execute_at: i64,                // 8 — v1.11 (execute_legacy allowed after this)
+    pub account_mints: [AccountMint; MAX_SNAPSHOT_MINTS], // 560 — v1.11 (BeneficiaryAccount mints)
+    pub stage_bounty_base: u64,                  // 8 — v1.11 (stake at this awakening)
     // ── Versioning — always the last field ──────────────────────────────────
     // schema_version tracks which program version wrote this account.
     // UPGRADE RULE: when adding new fields in a future version —
@@ -804,23 +3077,578 @@ pub struct GhostAccount {
     pub schema_version: u8,                      // 1 — v1.8+
 }
 
+impl GhostAccount {
+    // v1.11 fields are only meaningful once migrate_ghost has run. On an unmigrated v1.8
+    // account they overlay the old trailing schema_version byte and zeroed slack.
+    pub fn is_v111(&self) -> bool {
+        self.schema_version >= SCHEMA_VERSION_V111
+    }
+
+    // Every field of a freshly created ghost. Called by initialize_ghost and
+    // initialize_indexed_ghost (which sets ghost_index/indexed afterwards).
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(&mut self, owner: Pubkey, interval_seconds: i64, grace_period_seconds: i64, stake_amount: u64, bump: u8, vault_bump: u8, now: i64, execution_fee_bps: u16) {
+        self.owner = owner;
+        self.recovery_wallets = [None, None, None];
+        self.last_heartbeat = now;
+        self.interval_seconds = interval_seconds;
+        self.grace_period_seconds = grace_period_seconds;
+        self.awakened = false;
+        self.awakened_at = None;
+        self.executed = false;
+        self.executed_at = None;
+        self.staked_ghost = stake_amount;
+        self.bump = bump;
+        self.vault_bump = vault_bump;
+        self.registered_at = now;
+        self.ping_count = 0;
+        self.beneficiary_count = 0;
+        self.whole_vault_action = 0;
+        self.display_name = [0u8; 32];
+        self.image_uri = [0u8; 128];
+        self.whole_vault_recipient = None;
+        self.paused = false;
+        self.pending_owner = None;
+        for i in 0..10 {
+            self.beneficiaries[i] = Beneficiary::default();
+        }
+        self.beneficiary_ext = [BeneficiaryExt::default(); MAX_BENEFICIARIES];
+        self.residual_recipient = None;
+        self.guardian_threshold = 1;
+        self.proposal_count = 0;
+        self.guardian_delay_seconds = 0;
+        self.owner_ping_nonce = 0;
+        self.queued_guardian_actions = 0;
+        self.original_owner = owner;
+        self.pending_owner_since = 0;
+        self.ping_delegates = [PingDelegate::default(); MAX_PING_DELEGATES];
+        self.heartbeat_nonce = 0;
+        self.ghost_index = 0;
+        self.indexed = false;
+        self.beneficiary_accounts = 0;
+        self.next_beneficiary_id = 0;
+        self.disclaim_policy = DISCLAIM_POLICY_ESTATE;
+        self.rent_recipient = None;
+        self.execution_fee_bps = execution_fee_bps;
+        self.sol_staked = false;
+        self.sol_deposit = 0;
+        self.paused_until = 0;
+        self.warning_bps = 0;
+        self.final_notice_bps = 0;
+        self.warned_at = 0;
+        self.final_notice_at = 0;
+        self.attested_at = 0;
+        self.attested_execute_at = 0;
+        self.account_mints = [AccountMint::default(); MAX_SNAPSHOT_MINTS];
+        self.stage_bounty_base = 0;
+        // v1.8: set schema_version on new accounts so they never need migration.
+        // Future versions: update this line to use CURRENT_SCHEMA_VERSION (which you
+        // should update to point to the new SCHEMA_VERSION_VXX constant).
+        self.schema_version = CURRENT_SCHEMA_VERSION;
+    }
+
+    // Trailing PDA seed after the owner key: the u16 index for indexed ghosts, empty (so the
+    // legacy ["ghost", owner] address is unchanged) for everything else.
+    pub fn index_seed(&self) -> Vec<u8> {
+        if self.is_v111() && self.indexed { self.ghost_index.to_le_bytes().to_vec() } else { Vec::new() }
+    }
+
+    // The key the ghost and vault PDAs were derived from at init. Owner can only change on
+    // migrated accounts, so on older ones `owner` is still that key.
+    pub fn seed_owner(&self) -> &Pubkey {
+        if self.is_v111() { &self.original_owner } else { &self.owner }
+    }
+
+    pub fn is_sol_staked(&self) -> bool {
+        self.is_v111() && self.sol_staked
+    }
+
+    // Execution fee actually charged: the rate locked in at registration, or the current
+    // config rate if that is lower. A lower rate is written back, so the lock only ratchets
+    // down. Unmigrated accounts registered under EXECUTION_FEE_BPS.
+    pub fn fee_bps(&mut self, config: &ProtocolConfig) -> u64 {
+        if !self.is_v111() {
+            return (EXECUTION_FEE_BPS as u16).min(config.execution_fee_bps) as u64;
+        }
+        self.execution_fee_bps = self.execution_fee_bps.min(config.execution_fee_bps);
+        self.execution_fee_bps as u64
+    }
+
+    // A pre-v1.11 pause has no end; a v1.11 pause holds until paused_until.
+    pub fn is_paused(&self, now: i64) -> bool {
+        self.paused && (!self.is_v111() || now < self.paused_until)
+    }
+
+    // Start of the silence check_silence measures: the last heartbeat or the end of the
+    // last pause, whichever is later.
+    pub fn silence_start(&self) -> i64 {
+        if self.is_v111() { self.last_heartbeat.max(self.paused_until) } else { self.last_heartbeat }
+    }
+
+    pub fn has_warning_stage(&self) -> bool {
+        self.is_v111() && self.warning_bps > 0
+    }
+
+    pub fn has_final_notice_stage(&self) -> bool {
+        self.is_v111() && self.final_notice_bps > 0
+    }
+
+    // A warning counts only for the current silence — any later heartbeat or pause end resets it.
+    pub fn warning_issued(&self) -> bool {
+        self.is_v111() && self.warned_at > self.silence_start()
+    }
+
+    // A final notice counts only for the current awakening.
+    pub fn final_notice_issued(&self) -> bool {
+        self.is_v111() && self.awakened_at.is_some_and(|at| self.final_notice_at >= at)
+    }
+
+    // A guardian attestation was executed during the current awakening.
+    pub fn is_attested(&self) -> bool {
+        self.is_v111() && self.attested_at > 0 && self.awakened_at.is_some_and(|at| self.attested_at >= at)
+    }
+
+    // Guardians attested this awakening: only the owner showing up can undo it.
+    pub fn may_cancel_awakening(&self, by_owner: bool) -> bool {
+        by_owner || !self.is_attested()
+    }
+
+    // End of the current awakening's grace period: the attested deadline if a guardian
+    // attestation was executed during this awakening, else awakened_at + grace_period_seconds.
+    pub fn grace_end(&self) -> i64 {
+        if self.is_attested() { self.attested_execute_at } else { self.awakened_at.unwrap() + self.grace_period_seconds }
+    }
+
+    // Applies an executed AttestDeath and returns when execute_legacy becomes callable:
+    // `remaining_seconds` from now, but at least MIN_ATTESTATION_SAFETY_SECONDS and not before
+    // a live pause ends, and no later than the time-based path (check_silence as soon as
+    // possible, then the grace period). The pause itself stays; only the owner lifts it.
+    // No warning is recorded — the awakening skips that stage.
+    pub fn record_attestation(&mut self, remaining_seconds: i64, now: i64) -> i64 {
+        let time_based = if self.awakened {
+            self.grace_end()
+        } else {
+            (self.silence_start() + self.interval_seconds).max(now) + self.grace_period_seconds
+        };
+        let pause_end = if self.is_paused(now) { self.paused_until } else { now };
+        let execute_after = (now + remaining_seconds.max(MIN_ATTESTATION_SAFETY_SECONDS)).max(pause_end).min(time_based);
+        if !self.awakened {
+            self.awakened = true;
+            self.awakened_at = Some(now);
+            self.stage_bounty_base = self.remaining_stake();
+        }
+        self.attested_at = now;
+        self.attested_execute_at = execute_after;
+        execute_after
+    }
+
+    // check_silence's slice: SILENCE_BOUNTY_BPS minus the final notice slice if that stage is
+    // enabled. The warning's share stays in it, since it is only earned if the awakening follows.
+    pub fn awakening_bounty_bps(&self) -> u64 {
+        if self.has_final_notice_stage() { SILENCE_BOUNTY_BPS - FINAL_NOTICE_BOUNTY_BPS } else { SILENCE_BOUNTY_BPS }
+    }
+
+    // What the stage bounties are sliced from: the SOL deposit or the $GHOST stake left.
+    pub fn remaining_stake(&self) -> u64 {
+        if self.is_sol_staked() { self.sol_deposit } else { self.staked_ghost }
+    }
+
+    // Share of the stake abandon_ghost burns: 0 within the trial window after registered_at,
+    // then rising linearly over the ramp to BURN_ON_ABANDON_BPS.
+    pub fn abandon_burn_bps(&self, config: &ProtocolConfig, now: i64) -> u64 {
+        let past_trial = now.saturating_sub(self.registered_at).saturating_sub(config.abandon_trial_seconds);
+        if past_trial <= 0 {
+            return 0;
+        }
+        if past_trial >= config.abandon_burn_ramp_seconds {
+            return BURN_ON_ABANDON_BPS;
+        }
+        BURN_ON_ABANDON_BPS * past_trial as u64 / config.abandon_burn_ramp_seconds as u64
+    }
+
+    // Receiver of everything finalization frees: rent_recipient, else the estate recipient
+    // (a Transfer whole_vault_recipient, then residual_recipient), else the owner.
+    pub fn rent_destination(&self) -> Pubkey {
+        let whole_vault = if self.whole_vault_action == 0 { self.whole_vault_recipient } else { None };
+        self.rent_recipient.or(whole_vault).or(self.residual_recipient).unwrap_or(self.owner)
+    }
+
+    pub fn is_ping_delegate(&self, key: Pubkey, now: i64) -> bool {
+        self.is_v111() && self.ping_delegates.iter().any(|d| d.key == key && d.is_live(now))
+    }
+
+    pub fn is_vesting_slot(&self, index: usize) -> bool {
+        self.is_v111() && self.beneficiary_ext[index].is_vesting()
+    }
+
+    pub fn is_contingent_slot(&self, index: usize) -> bool {
+        self.is_v111() && self.beneficiary_ext[index].has_contingent()
+    }
+
+    pub fn is_disclaimed_slot(&self, index: usize) -> bool {
+        self.is_v111() && self.beneficiary_ext[index].disclaimed
+    }
+
+    // Amount of embedded slot `index` already released by claim_vested.
+    pub fn slot_claimed(&self, index: usize) -> u64 {
+        if self.is_v111() { self.beneficiary_ext[index].claimed } else { 0 }
+    }
+
+    pub fn beneficiary_account_count(&self) -> u32 {
+        if self.is_v111() { self.beneficiary_accounts } else { 0 }
+    }
+
+    pub fn all_slots_executed(&self) -> bool {
+        self.beneficiaries[..self.beneficiary_count as usize].iter().all(|b| b.executed)
+    }
+
+    // Spreads slot `index`'s amount over the other unpaid, undisclaimed Transfer slots of
+    // the same mint in proportion to their amounts. Snapshot `committed` is unchanged, so
+    // entitlements stay within balance; rounding dust is left to the estate. Returns false
+    // (nothing changed) when there is no such slot.
+    pub fn redistribute_slot(&mut self, index: usize) -> Result<bool> {
+        let disclaimed = self.beneficiaries[index];
+        let takers: Vec<usize> = (0..self.beneficiary_count as usize)
+            .filter(|&i| {
+                let b = &self.beneficiaries[i];
+                i != index && !b.executed && b.action == 0 && b.token_mint == disclaimed.token_mint && !self.beneficiary_ext[i].disclaimed
+            })
+            .collect();
+        let total: u128 = takers.iter().map(|&i| self.beneficiaries[i].amount as u128).sum();
+        if total == 0 { return Ok(false); }
+        for &i in &takers {
+            let extra = (disclaimed.amount as u128 * self.beneficiaries[i].amount as u128 / total) as u64;
+            self.beneficiaries[i].amount = self.beneficiaries[i].amount.checked_add(extra).ok_or(GhostError::Overflow)?;
+        }
+        Ok(true)
+    }
+
+    pub fn guardian_count(&self) -> u8 {
+        self.recovery_wallets.iter().filter(|w| w.is_some()).count() as u8
+    }
+
+    // Approvals needed per guardian action. Unmigrated accounts keep single-guardian behavior.
+    pub fn guardian_quorum(&self) -> u8 {
+        if self.is_v111() { self.guardian_threshold.max(1) } else { 1 }
+    }
+
+    pub fn guardian_delay(&self) -> i64 {
+        if self.is_v111() { self.guardian_delay_seconds } else { 0 }
+    }
+
+    // The legacy single-guardian instructions only run with a 1-of-N quorum and no time-lock.
+    pub fn guardian_actions_direct(&self) -> bool {
+        self.guardian_quorum() <= 1 && self.guardian_delay() == 0
+    }
+
+    // An owner-signed heartbeat invalidates every open proposal. Returns how many queued
+    // actions that vetoed.
+    pub fn record_owner_ping(&mut self) -> u8 {
+        if !self.is_v111() { return 0; }
+        self.owner_ping_nonce += 1;
+        std::mem::take(&mut self.queued_guardian_actions)
+    }
+
+    // Distinct mints the execution snapshot would have to hold if `mint` were added, across
+    // embedded slots (except `skip_slot`) and beneficiary accounts (except one of `skip_account`).
+    pub fn distinct_mints_with(&self, mint: Option<Pubkey>, skip_slot: Option<usize>, skip_account: Option<Option<Pubkey>>) -> usize {
+        let mut mints: Vec<Option<Pubkey>> = vec![mint];
+        let slots = (0..self.beneficiary_count as usize).filter(|&i| Some(i) != skip_slot).map(|i| self.beneficiaries[i].token_mint);
+        let accounts = self.account_mints.iter()
+            .filter(|m| m.count > 1 || (m.count == 1 && Some(m.mint) != skip_account))
+            .map(|m| m.mint);
+        for m in slots.chain(accounts) {
+            if !mints.contains(&m) { mints.push(m); }
+        }
+        mints.len()
+    }
+
+    pub fn track_account_mint(&mut self, mint: Option<Pubkey>) -> Result<()> {
+        let entry = match self.account_mints.iter().position(|m| m.count > 0 && m.mint == mint) {
+            Some(i) => i,
+            None => self.account_mints.iter().position(|m| m.count == 0).ok_or(GhostError::TooManySnapshotMints)?,
+        };
+        self.account_mints[entry].mint = mint;
+        self.account_mints[entry].count = self.account_mints[entry].count.checked_add(1).ok_or(GhostError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn untrack_account_mint(&mut self, mint: Option<Pubkey>) {
+        if let Some(entry) = self.account_mints.iter_mut().find(|m| m.count > 0 && m.mint == mint) {
+            entry.count -= 1;
+        }
+    }
+
+    pub fn slot_matches(&self, index: u8, recipient: Pubkey, token_mint: Option<Pubkey>) -> bool {
+        (index as usize) < self.beneficiary_count as usize
+            && self.beneficiaries[index as usize].recipient == recipient
+            && self.beneficiaries[index as usize].token_mint == token_mint
+    }
+
+    pub fn remove_beneficiary_at(&mut self, index: u8) -> Result<()> {
+        require!((index as usize) < self.beneficiary_count as usize, GhostError::InvalidBeneficiary);
+        let count = self.beneficiary_count as usize;
+        for i in (index as usize)..(count - 1) {
+            self.beneficiaries[i] = self.beneficiaries[i + 1];
+            self.beneficiary_ext[i] = self.beneficiary_ext[i + 1];
+        }
+        self.beneficiary_count -= 1;
+        Ok(())
+    }
+
+    // Returns the number of slots that were cleared.
+    pub fn clear_beneficiaries(&mut self) -> u8 {
+        let cleared_count = self.beneficiary_count;
+        for i in 0..MAX_BENEFICIARIES {
+            self.beneficiaries[i] = Beneficiary::default();
+            self.beneficiary_ext[i] = BeneficiaryExt::default();
+        }
+        self.beneficiary_count = 0;
+        cleared_count
+    }
+
+    // Returns the previous recipient. Callers validate `action` first.
+    pub fn set_whole_vault(&mut self, recipient: Option<Pubkey>, action: u8) -> Option<Pubkey> {
+        let previous = self.whole_vault_recipient;
+        self.whole_vault_recipient = recipient;
+        self.whole_vault_action = if recipient.is_some() { action } else { 0 };
+        previous
+    }
+}
+
+// v1.11: per-mint figures frozen at execute_legacy. `paid` counts gross vault outflow
+// (fees and burns included) so clients can show progress without replaying events.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub struct MintSnapshot {
+    pub mint: Option<Pubkey>,                    // 33 — None = native SOL
+    pub balance: u64,                            // 8 — distributable vault balance at snapshot
+    pub committed: u64,                          // 8 — sum of unpaid slot amounts for this mint
+    pub paid: u64,                               // 8
+    pub account_outstanding: u64,                // 8 — unpaid BeneficiaryAccount amounts (subset of committed)
+}
+
+impl MintSnapshot {
+    // Fully funded mints pay `amount`; short mints pay amount * balance / committed to every
+    // slot, rounded down so the sum can never exceed `balance`.
+    pub fn entitlement(&self, amount: u64) -> u64 {
+        if self.committed <= self.balance {
+            amount
+        } else {
+            (amount as u128 * self.balance as u128 / self.committed as u128) as u64
+        }
+    }
+}
+
+// v1.11: what the whole-vault token sweeps have moved out of one mint so far.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub struct SweptMint {
+    pub mint: Pubkey,                            // 32
+    pub amount: u64,                             // 8 — gross, fee included
+    pub closed: bool,                            // 1 — vault token account closed by the last sweep
+}
+
+#[account]
+pub struct ExecutionSnapshot {
+    pub ghost: Pubkey,                           // 32
+    pub taken_at: i64,                           // 8
+    pub bump: u8,                                // 1
+    pub pending_tallies: u32,                    // 4 — BeneficiaryAccounts not yet folded in
+    pub accounts_unpaid: u32,                    // 4 — tallied BeneficiaryAccounts not yet paid
+    pub mints: Vec<MintSnapshot>,                // 4 + MAX_SNAPSHOT_MINTS * MINT_SNAPSHOT_SIZE
+    pub swept: Vec<SweptMint>,                   // 4 + MAX_SWEPT_MINTS * SWEPT_MINT_SIZE
+}
+
+impl ExecutionSnapshot {
+    pub fn entry(&self, mint: Option<Pubkey>) -> Option<&MintSnapshot> {
+        self.mints.iter().find(|m| m.mint == mint)
+    }
+
+    // Entitlements are only final once every BeneficiaryAccount has been tallied.
+    pub fn require_tallied(&self) -> Result<()> {
+        require!(self.pending_tallies == 0, GhostError::BeneficiaryTallyPending);
+        Ok(())
+    }
+
+    pub fn entitlement(&self, mint: Option<Pubkey>, amount: u64) -> Result<u64> {
+        self.require_tallied()?;
+        let entry = self.entry(mint).ok_or(GhostError::MintNotInSnapshot)?;
+        Ok(entry.entitlement(amount))
+    }
+
+    // `amount` is the slot's committed amount, not what it was paid.
+    pub fn record_account_paid(&mut self, mint: Option<Pubkey>, amount: u64) -> Result<()> {
+        let entry = self.mints.iter_mut().find(|m| m.mint == mint).ok_or(GhostError::MintNotInSnapshot)?;
+        entry.account_outstanding = entry.account_outstanding.saturating_sub(amount);
+        self.accounts_unpaid = self.accounts_unpaid.saturating_sub(1);
+        Ok(())
+    }
+
+    pub fn record_paid(&mut self, mint: Option<Pubkey>, amount: u64) -> Result<()> {
+        let entry = self.mints.iter_mut().find(|m| m.mint == mint).ok_or(GhostError::MintNotInSnapshot)?;
+        entry.paid = entry.paid.checked_add(amount).ok_or(GhostError::Overflow)?;
+        Ok(())
+    }
+
+    // Every token mint the vault is known to have held — snapshot mints and anything swept —
+    // has had its vault token account closed.
+    pub fn vault_token_accounts_closed(&self) -> bool {
+        self.swept.iter().all(|s| s.closed)
+            && self.mints.iter().filter_map(|m| m.mint).all(|mint| self.swept.iter().any(|s| s.mint == mint))
+    }
+
+    pub fn record_sweep(&mut self, mint: Pubkey, amount: u64, closed: bool) -> Result<()> {
+        match self.swept.iter_mut().find(|s| s.mint == mint) {
+            Some(entry) => {
+                entry.amount = entry.amount.checked_add(amount).ok_or(GhostError::Overflow)?;
+                entry.closed = closed;
+            }
+            None => {
+                require!(self.swept.len() < MAX_SWEPT_MINTS, GhostError::TooManySweptMints);
+                self.swept.push(SweptMint { mint, amount, closed });
+            }
+        }
+        Ok(())
+    }
+}
+
+// v1.11: a guardian action awaiting quorum. `destination` for RecoveryWithdraw is the token
+// account that will receive the funds — fixed at proposal time so approvers sign off on it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum GuardianAction {
+    RecoveryWithdraw { mint: Pubkey, amount: u64, destination: Pubkey },
+    // recipient/token_mint pin the slot: execution fails if `index` no longer holds them.
+    RemoveBeneficiary { index: u8, recipient: Pubkey, token_mint: Option<Pubkey> },
+    ClearBeneficiaries,
+    SetWholeVaultRecipient { recipient: Option<Pubkey>, action: u8 },
+    // v1.11: the owner is known dead or incapacitated. 0 = execute as soon as the safety window allows.
+    AttestDeath { remaining_seconds: i64 },
+}
+
+#[account]
+pub struct GuardianProposal {
+    pub ghost: Pubkey,                           // 32
+    pub id: u64,                                 // 8
+    pub proposer: Pubkey,                        // 32
+    pub action: GuardianAction,                  // GUARDIAN_ACTION_SIZE
+    pub guardians: [Option<Pubkey>; 3],          // 99 — recovery wallet set at proposal time
+    pub approvals: u8,                           // 1 — bitmap over recovery_wallets indices
+    pub created_at: i64,                         // 8
+    pub expires_at: i64,                         // 8
+    pub executed: bool,                          // 1
+    pub bump: u8,                                // 1
+    pub ping_nonce: u64,                         // 8 — ghost.owner_ping_nonce when opened
+    pub executable_at: Option<i64>,              // 9 — set when quorum is reached
+    pub vetoed: bool,                            // 1
+}
+
+impl GuardianProposal {
+    // Reached quorum, not executed, and not vetoed by instruction or owner ping —
+    // i.e. currently counted in ghost.queued_guardian_actions.
+    pub fn is_queued(&self, ghost: &GhostAccount) -> bool {
+        self.executable_at.is_some() && !self.executed && !self.vetoed && self.ping_nonce == ghost.owner_ping_nonce
+    }
+
+    // Starts the owner veto window once approvals reach quorum. The execution window
+    // (PROPOSAL_TTL_SECONDS) is measured from the end of the delay.
+    pub fn queue_if_quorum(&mut self, ghost: &mut GhostAccount, now: i64) {
+        if self.executable_at.is_some() || (self.approvals.count_ones() as u8) < ghost.guardian_quorum() { return; }
+        let executable_at = now + ghost.guardian_delay();
+        self.executable_at = Some(executable_at);
+        self.expires_at = executable_at + PROPOSAL_TTL_SECONDS;
+        ghost.queued_guardian_actions = ghost.queued_guardian_actions.saturating_add(1);
+        emit!(GuardianActionQueued { soul: ghost.owner, proposal_id: self.id, executable_at });
+        msg!("Guardian proposal #{} queued, executable at {}", self.id, executable_at);
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(init, payer = signer, space = PROTOCOL_CONFIG_SPACE, seeds = [CONFIG_SEED], bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ GhostError::Unauthorized)]
+    pub program: Program<'info, crate::program::GhostProtocol>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(signer.key()) @ GhostError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)] pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ GhostError::Unauthorized)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolAdmin<'info> {
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ GhostError::Unauthorized)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    pub admin: Signer<'info>,
+    pub new_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeSolGhost<'info> {
+    // space = GHOST_ACCOUNT_SPACE — same account as InitializeGhost, no stake vault
+    #[account(init, payer = signer, space = GHOST_ACCOUNT_SPACE, seeds = [GHOST_SEED, signer.key().as_ref()], bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA — bump derivation only
+    #[account(seeds = [VAULT_SEED, signer.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK: Bot operations wallet — receives registration fee. Validated by address constraint.
+    #[account(mut, constraint = bot_ops_wallet.key() == config.bot_ops_wallet @ GhostError::Unauthorized)]
+    pub bot_ops_wallet: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGhost<'info> {
+    // space = GHOST_ACCOUNT_SPACE (2816 for v1.11) — update this when GHOST_ACCOUNT_SPACE grows
+    #[account(init, payer = signer, space = GHOST_ACCOUNT_SPACE, seeds = [GHOST_SEED, signer.key().as_ref()], bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA — bump derivation only
+    #[account(seeds = [VAULT_SEED, signer.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+    pub ghost_mint: InterfaceAccount<'info, Mint>,
+    #[account(init, payer = signer, seeds = [b"stake_vault", signer.key().as_ref()], bump, token::mint = ghost_mint, token::authority = ghost, token::token_program = token_program)]
+    pub ghost_stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = ghost_mint, token::authority = signer, token::token_program = token_program)]
+    pub signer_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK: Bot operations wallet — receives registration fee. Validated by address constraint.
+    #[account(mut, constraint = bot_ops_wallet.key() == config.bot_ops_wallet @ GhostError::Unauthorized)]
+    pub bot_ops_wallet: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
-pub struct InitializeGhost<'info> {
-    // space = GHOST_ACCOUNT_SPACE (1221 for v1.8) — update this when GHOST_ACCOUNT_SPACE grows
-    #[account(init, payer = signer, space = GHOST_ACCOUNT_SPACE, seeds = [GHOST_SEED, signer.key().as_ref()], bump)]
+#[instruction(ghost_index: u16)]
+pub struct InitializeIndexedGhost<'info> {
+    #[account(init, payer = signer, space = GHOST_ACCOUNT_SPACE, seeds = [GHOST_SEED, signer.key().as_ref(), &ghost_index.to_le_bytes()], bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     /// CHECK: Vault PDA — bump derivation only
-    #[account(seeds = [VAULT_SEED, signer.key().as_ref()], bump)]
+    #[account(seeds = [VAULT_SEED, signer.key().as_ref(), &ghost_index.to_le_bytes()], bump)]
     pub vault: UncheckedAccount<'info>,
     pub ghost_mint: InterfaceAccount<'info, Mint>,
-    #[account(init, payer = signer, seeds = [b"stake_vault", signer.key().as_ref()], bump, token::mint = ghost_mint, token::authority = ghost, token::token_program = token_program)]
+    #[account(init, payer = signer, seeds = [b"stake_vault", signer.key().as_ref(), &ghost_index.to_le_bytes()], bump, token::mint = ghost_mint, token::authority = ghost, token::token_program = token_program)]
     pub ghost_stake_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(mut, token::mint = ghost_mint, token::authority = signer, token::token_program = token_program)]
     pub signer_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
     /// CHECK: Bot operations wallet — receives registration fee. Validated by address constraint.
-    #[account(mut, constraint = bot_ops_wallet.key() == BOT_OPS_WALLET @ GhostError::Unauthorized)]
+    #[account(mut, constraint = bot_ops_wallet.key() == config.bot_ops_wallet @ GhostError::Unauthorized)]
     pub bot_ops_wallet: UncheckedAccount<'info>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
@@ -828,68 +3656,207 @@ pub struct InitializeGhost<'info> {
 
 #[derive(Accounts)]
 pub struct Ping<'info> {
-    // v1.10: seeds use ghost.owner.as_ref() so PDA derivation tracks current owner
-    // after transfer_ownership. Constraint preserves owner-only auth.
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    // v1.11: seeds use ghost.seed_owner() (stable across transfer_ownership). Signer is
+    // checked in the instruction — owner or a live ping delegate.
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     #[account(mut)] pub signer: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct PingWithSignature<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: instructions sysvar — address-checked, read via load_instruction_at_checked
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub relayer: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct ManageBeneficiaries<'info> {
-    // v1.10: seed via ghost.owner so post-transfer_ownership the new owner can manage
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    // v1.11: seed via ghost.seed_owner() so post-transfer_ownership the new owner can manage
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(mut)] pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddBeneficiaryAccount<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(init, payer = signer, space = BENEFICIARY_ACCOUNT_SPACE, seeds = [BENEFICIARY_SEED, ghost.key().as_ref(), &ghost.next_beneficiary_id.to_le_bytes()], bump)]
+    pub beneficiary_account: Box<Account<'info, BeneficiaryAccount>>,
+    #[account(mut)] pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageBeneficiaryAccount<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(mut, seeds = [BENEFICIARY_SEED, ghost.key().as_ref(), &beneficiary_account.id.to_le_bytes()], bump = beneficiary_account.bump, has_one = ghost)]
+    pub beneficiary_account: Box<Account<'info, BeneficiaryAccount>>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveBeneficiaryAccount<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
     pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(mut, seeds = [BENEFICIARY_SEED, ghost.key().as_ref(), &beneficiary_account.id.to_le_bytes()], bump = beneficiary_account.bump, has_one = ghost, close = signer)]
+    pub beneficiary_account: Box<Account<'info, BeneficiaryAccount>>,
     #[account(mut)] pub signer: Signer<'info>,
 }
 
+// v1.11: same remaining_accounts contract as ExecuteLegacy (only read for a new mint)
+#[derive(Accounts)]
+pub struct TallyBeneficiaryAccount<'info> {
+    #[account(seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA — read-only, owner of the vault token accounts in remaining_accounts
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    #[account(mut, seeds = [BENEFICIARY_SEED, ghost.key().as_ref(), &beneficiary_account.id.to_le_bytes()], bump = beneficiary_account.bump, has_one = ghost)]
+    pub beneficiary_account: Box<Account<'info, BeneficiaryAccount>>,
+    pub caller: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct GuardianManageBeneficiaries<'info> {
-    #[account(mut, seeds = [GHOST_SEED, owner.key().as_ref()], bump = ghost.bump)]
+    #[account(mut, seeds = [GHOST_SEED, owner.key().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
-    /// CHECK: Owner pubkey — PDA derivation only
+    /// CHECK: ghost.seed_owner() (original owner) — PDA derivation only
     pub owner: UncheckedAccount<'info>,
     #[account(mut)] pub recovery_wallet: Signer<'info>,
 }
 
-// v1.10: AcceptOwnership Accounts struct removed (instruction deleted — see comment
-// above transfer_ownership). Struct definitions for `Accounts` aren't part of any
-// runtime state, so removing this is safe and does not affect existing accounts.
+#[derive(Accounts)]
+pub struct ProposeGuardianAction<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(init, payer = guardian, space = GUARDIAN_PROPOSAL_SPACE, seeds = [PROPOSAL_SEED, ghost.key().as_ref(), &ghost.proposal_count.to_le_bytes()], bump)]
+    pub proposal: Box<Account<'info, GuardianProposal>>,
+    // Validated in instruction (must be one of ghost.recovery_wallets)
+    #[account(mut)] pub guardian: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveGuardianAction<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(mut, seeds = [PROPOSAL_SEED, ghost.key().as_ref(), &proposal.id.to_le_bytes()], bump = proposal.bump, has_one = ghost)]
+    pub proposal: Box<Account<'info, GuardianProposal>>,
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteGuardianAction<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(mut, seeds = [PROPOSAL_SEED, ghost.key().as_ref(), &proposal.id.to_le_bytes()], bump = proposal.bump, has_one = ghost)]
+    pub proposal: Box<Account<'info, GuardianProposal>>,
+    pub guardian: Signer<'info>,
+    // RecoveryWithdraw only — validated against the proposal in the instruction
+    /// CHECK: Vault PDA — address re-derived in instruction
+    pub vault: Option<UncheckedAccount<'info>>,
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub destination_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct VetoGuardianAction<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(mut, seeds = [PROPOSAL_SEED, ghost.key().as_ref(), &proposal.id.to_le_bytes()], bump = proposal.bump, has_one = ghost)]
+    pub proposal: Box<Account<'info, GuardianProposal>>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireGuardianProposal<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(mut, seeds = [PROPOSAL_SEED, ghost.key().as_ref(), &proposal.id.to_le_bytes()], bump = proposal.bump, has_one = ghost, has_one = proposer, close = proposer)]
+    pub proposal: Box<Account<'info, GuardianProposal>>,
+    /// CHECK: rent destination — must match proposal.proposer (has_one)
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOwnership<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.pending_owner == Some(new_owner.key()) @ GhostError::NoPendingOwnerTransfer)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    pub new_owner: Signer<'info>,
+}
 
 #[derive(Accounts)]
 pub struct CheckSilence<'info> {
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump)]
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     #[account(mut)] pub caller: Signer<'info>,
-    pub ghost_mint: InterfaceAccount<'info, Mint>,
+    // v1.11: the stake accounts are None for SOL-deposit ghosts (bounty paid in lamports)
+    pub ghost_mint: Option<InterfaceAccount<'info, Mint>>,
     #[account(mut, token::mint = ghost_mint, token::token_program = token_program)]
-    pub ghost_stake_vault: InterfaceAccount<'info, TokenAccount>,
+    pub ghost_stake_vault: Option<InterfaceAccount<'info, TokenAccount>>,
     #[account(mut, token::mint = ghost_mint, token::token_program = token_program)]
-    pub caller_token_account: InterfaceAccount<'info, TokenAccount>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub caller_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
 pub struct CancelAwakening<'info> {
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump)]
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     #[account(mut)] pub signer: Signer<'info>,
 }
 
+// v1.11: remaining_accounts = vault token accounts for every beneficiary mint (any order)
 #[derive(Accounts)]
 pub struct ExecuteLegacy<'info> {
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump)]
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
-    pub caller: Signer<'info>,
+    /// CHECK: Vault PDA — read-only, balances feed share resolution and the snapshot
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(init, payer = caller, space = EXECUTION_SNAPSHOT_SPACE, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    #[account(mut)] pub caller: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// v1.11: same remaining_accounts contract as ExecuteLegacy
+#[derive(Accounts)]
+pub struct TakeExecutionSnapshot<'info> {
+    #[account(seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA — read-only, balances feed the snapshot
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(init, payer = caller, space = EXECUTION_SNAPSHOT_SPACE, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    #[account(mut)] pub caller: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct ExecuteTransfer<'info> {
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump)]
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     /// CHECK: Vault PDA authority
-    #[account(seeds = [VAULT_SEED, ghost.owner.as_ref()], bump = ghost.vault_bump)]
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
     pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
     // v1.10: Box<> all InterfaceAccounts to move them off the stack —
     // the v1.10 constraint additions pushed this struct over Solana's 4KB stack frame.
     #[account(mut)] pub token_mint: Box<InterfaceAccount<'info, Mint>>,
@@ -903,19 +3870,114 @@ pub struct ExecuteTransfer<'info> {
     #[account(mut, token::mint = token_mint, token::authority = recipient, token::token_program = token_program)]
     pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     pub token_program: Interface<'info, TokenInterface>,
-    /// Protocol fee token account — must match mint and be owned by PROTOCOL_FEE_WALLET
-    #[account(mut, token::mint = token_mint, token::authority = PROTOCOL_FEE_WALLET, token::token_program = token_program)]
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// Protocol fee token account — must match mint and be owned by config.fee_wallet
+    #[account(mut, token::mint = token_mint, token::authority = config.fee_wallet, token::token_program = token_program)]
+    pub fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub caller: Signer<'info>,
+    // v1.11: set to pay a BeneficiaryAccount instead of embedded slot `beneficiary_index`
+    #[account(mut, has_one = ghost)]
+    pub beneficiary_account: Option<Box<Account<'info, BeneficiaryAccount>>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA authority
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    #[account(mut)] pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, token::mint = token_mint, token::authority = vault, token::token_program = token_program)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: validated in instruction (pubkey must match stored beneficiary.recipient)
+    pub recipient: UncheckedAccount<'info>,
+    #[account(mut, token::mint = token_mint, token::authority = recipient, token::token_program = token_program)]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// Protocol fee token account — must match mint and be owned by config.fee_wallet
+    #[account(mut, token::mint = token_mint, token::authority = config.fee_wallet, token::token_program = token_program)]
+    pub fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteContingentTransfer<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA authority
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    #[account(mut)] pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, token::mint = token_mint, token::authority = vault, token::token_program = token_program)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: validated in instruction (pubkey must match the slot's contingent_recipient)
+    pub contingent_recipient: UncheckedAccount<'info>,
+    #[account(mut, token::mint = token_mint, token::authority = contingent_recipient, token::token_program = token_program)]
+    pub contingent_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// Protocol fee token account — must match mint and be owned by config.fee_wallet
+    #[account(mut, token::mint = token_mint, token::authority = config.fee_wallet, token::token_program = token_program)]
     pub fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     pub caller: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct DisclaimBeneficiary<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    // The disclaimant — validated in instruction (pubkey must match stored beneficiary.recipient)
+    pub beneficiary: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBeneficiary<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA authority
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    #[account(mut)] pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, token::mint = token_mint, token::authority = vault, token::token_program = token_program)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    // The claimant — validated in instruction (pubkey must match stored beneficiary.recipient)
+    #[account(mut)] pub beneficiary: Signer<'info>,
+    // Canonical ATA only — created here if missing, rent paid by the beneficiary
+    #[account(init_if_needed, payer = beneficiary, associated_token::mint = token_mint, associated_token::authority = beneficiary, associated_token::token_program = token_program)]
+    pub beneficiary_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK: Protocol fee wallet — ATA authority only. Validated by address constraint.
+    #[account(constraint = fee_wallet.key() == config.fee_wallet @ GhostError::Unauthorized)]
+    pub fee_wallet: UncheckedAccount<'info>,
+    // Fee wallet's ATA — also created if missing so a claim never depends on the keeper
+    #[account(init_if_needed, payer = beneficiary, associated_token::mint = token_mint, associated_token::authority = fee_wallet, associated_token::token_program = token_program)]
+    pub fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct ExecuteWholeVaultTransfer<'info> {
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump)]
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     /// CHECK: Vault PDA authority
-    #[account(seeds = [VAULT_SEED, ghost.owner.as_ref()], bump = ghost.vault_bump)]
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
     pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
     // v1.10: Box<> all InterfaceAccounts — required to fit Solana's 4KB stack frame
     // after adding token::authority constraints (build error otherwise).
     #[account(mut)] pub token_mint: Box<InterfaceAccount<'info, Mint>>,
@@ -928,19 +3990,42 @@ pub struct ExecuteWholeVaultTransfer<'info> {
     #[account(mut, token::mint = token_mint, token::authority = recipient, token::token_program = token_program)]
     pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     pub token_program: Interface<'info, TokenInterface>,
-    /// Protocol fee token account — must match mint and be owned by PROTOCOL_FEE_WALLET
-    #[account(mut, token::mint = token_mint, token::authority = PROTOCOL_FEE_WALLET, token::token_program = token_program)]
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// Protocol fee token account — must match mint and be owned by config.fee_wallet
+    #[account(mut, token::mint = token_mint, token::authority = config.fee_wallet, token::token_program = token_program)]
     pub fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     pub caller: Signer<'info>,
 }
 
+// v1.11: the per-mint accounts come in remaining_accounts — see execute_whole_vault_sweep
+#[derive(Accounts)]
+pub struct ExecuteWholeVaultSweep<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA authority
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK: validated in instruction (pubkey must match stored whole_vault_recipient).
+    /// Writable — receives the rent of closed vault token accounts.
+    #[account(mut)] pub recipient: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub caller: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct ExecuteWholeVaultBurn<'info> {
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump)]
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     /// CHECK: Vault PDA authority
-    #[account(seeds = [VAULT_SEED, ghost.owner.as_ref()], bump = ghost.vault_bump)]
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
     pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
     #[account(mut)] pub token_mint: InterfaceAccount<'info, Mint>,
     // v1.10: vault_token_account must be authorized by the vault PDA
     #[account(mut, token::mint = token_mint, token::authority = vault, token::token_program = token_program)]
@@ -951,23 +4036,170 @@ pub struct ExecuteWholeVaultBurn<'info> {
 
 #[derive(Accounts)]
 pub struct ExecuteBurn<'info> {
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump)]
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     /// CHECK: Vault PDA authority
-    #[account(seeds = [VAULT_SEED, ghost.owner.as_ref()], bump = ghost.vault_bump)]
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
     pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
     #[account(mut)] pub mint: InterfaceAccount<'info, Mint>,
     // v1.10: vault_token_account must be authorized by the vault PDA
     #[account(mut, token::mint = mint, token::authority = vault, token::token_program = token_program)]
     pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
     pub token_program: Interface<'info, TokenInterface>,
     pub caller: Signer<'info>,
+    // v1.11: set to burn for a BeneficiaryAccount instead of embedded slot `beneficiary_index`
+    #[account(mut, has_one = ghost)]
+    pub beneficiary_account: Option<Box<Account<'info, BeneficiaryAccount>>>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSolTransfer<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA — holds the lamports, signs the system transfer
+    #[account(mut, seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    /// CHECK: validated in instruction (pubkey must match stored beneficiary.recipient)
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK: Protocol fee wallet — receives the lamport fee. Validated by address constraint.
+    #[account(mut, constraint = fee_wallet.key() == config.fee_wallet @ GhostError::Unauthorized)]
+    pub fee_wallet: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteWholeVaultSolTransfer<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA — holds the lamports, signs the system transfer
+    #[account(mut, seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    /// CHECK: validated in instruction (pubkey must match stored whole_vault_recipient)
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK: Protocol fee wallet — receives the lamport fee. Validated by address constraint.
+    #[account(mut, constraint = fee_wallet.key() == config.fee_wallet @ GhostError::Unauthorized)]
+    pub fee_wallet: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteResidualSweep<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA authority
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    #[account(mut)] pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, token::mint = token_mint, token::authority = vault, token::token_program = token_program)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: validated in instruction (pubkey must match stored residual_recipient)
+    pub recipient: UncheckedAccount<'info>,
+    #[account(mut, token::mint = token_mint, token::authority = recipient, token::token_program = token_program)]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// Protocol fee token account — must match mint and be owned by config.fee_wallet
+    #[account(mut, token::mint = token_mint, token::authority = config.fee_wallet, token::token_program = token_program)]
+    pub fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteResidualSolSweep<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA — holds the lamports, signs the system transfer
+    #[account(mut, seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    /// CHECK: validated in instruction (pubkey must match stored residual_recipient)
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK: Protocol fee wallet — receives the lamport fee. Validated by address constraint.
+    #[account(mut, constraint = fee_wallet.key() == config.fee_wallet @ GhostError::Unauthorized)]
+    pub fee_wallet: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVaultTokenAccount<'info> {
+    #[account(seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA authority
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, token::mint = token_mint, token::authority = vault, token::token_program = token_program)]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: pinned to ghost.rent_destination()
+    #[account(mut, address = ghost.rent_destination() @ GhostError::WrongRecipient)]
+    pub rent_recipient: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBeneficiaryAccount<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(mut, seeds = [BENEFICIARY_SEED, ghost.key().as_ref(), &beneficiary_account.id.to_le_bytes()], bump = beneficiary_account.bump, has_one = ghost, close = rent_recipient)]
+    pub beneficiary_account: Box<Account<'info, BeneficiaryAccount>>,
+    /// CHECK: pinned to ghost.rent_destination()
+    #[account(mut, address = ghost.rent_destination() @ GhostError::WrongRecipient)]
+    pub rent_recipient: UncheckedAccount<'info>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeGhost<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, close = rent_recipient)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA — its lamport reserve is returned
+    #[account(mut, seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [SNAPSHOT_SEED, ghost.key().as_ref()], bump = snapshot.bump, close = rent_recipient)]
+    pub snapshot: Box<Account<'info, ExecutionSnapshot>>,
+    /// CHECK: pinned to ghost.rent_destination()
+    #[account(mut, address = ghost.rent_destination() @ GhostError::WrongRecipient)]
+    pub rent_recipient: UncheckedAccount<'info>,
+    // The stake accounts are None for SOL-deposit ghosts
+    pub ghost_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut, seeds = [b"stake_vault", ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump, token::mint = ghost_mint, token::authority = ghost, token::token_program = token_program)]
+    pub ghost_stake_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut, token::mint = ghost_mint, token::authority = rent_recipient, token::token_program = token_program)]
+    pub rent_recipient_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub caller: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct DepositToVault<'info> {
-    // v1.10: seed via ghost.owner so post-transfer_ownership the owner can deposit
-    #[account(seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    // v1.11: seed via ghost.seed_owner() so post-transfer_ownership the owner can deposit
+    #[account(seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     #[account(mut)] pub signer: Signer<'info>,
     pub ghost_mint: InterfaceAccount<'info, Mint>,
@@ -980,11 +4212,11 @@ pub struct DepositToVault<'info> {
 
 #[derive(Accounts)]
 pub struct WithdrawFromVault<'info> {
-    // v1.10: seed via ghost.owner; auth still gated by constraint
-    #[account(seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    // v1.11: seed via ghost.seed_owner(); auth still gated by constraint
+    #[account(seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     /// CHECK: Vault PDA authority
-    #[account(seeds = [VAULT_SEED, ghost.owner.as_ref()], bump = ghost.vault_bump)]
+    #[account(seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
     pub vault: UncheckedAccount<'info>,
     #[account(mut)] pub signer: Signer<'info>,
     pub ghost_mint: InterfaceAccount<'info, Mint>,
@@ -998,14 +4230,37 @@ pub struct WithdrawFromVault<'info> {
     pub token_program: Interface<'info, TokenInterface>,
 }
 
+#[derive(Accounts)]
+pub struct DepositSolToVault<'info> {
+    #[account(seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA — receives lamports
+    #[account(mut, seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)] pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSolFromVault<'info> {
+    #[account(seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    /// CHECK: Vault PDA — holds the lamports, signs the system transfer
+    #[account(mut, seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    // Lamports always go back to the signing owner — no destination account to swap.
+    #[account(mut)] pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct RecoveryWithdraw<'info> {
-    #[account(mut, seeds = [GHOST_SEED, owner.key().as_ref()], bump = ghost.bump)]
+    #[account(mut, seeds = [GHOST_SEED, owner.key().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
     pub ghost: Box<Account<'info, GhostAccount>>,
-    /// CHECK: Owner pubkey — PDA derivation
+    /// CHECK: ghost.seed_owner() (original owner) — PDA derivation
     pub owner: UncheckedAccount<'info>,
     /// CHECK: Vault PDA
-    #[account(seeds = [VAULT_SEED, owner.key().as_ref()], bump = ghost.vault_bump)]
+    #[account(seeds = [VAULT_SEED, owner.key().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
     pub vault: UncheckedAccount<'info>,
     #[account(mut)] pub recovery_wallet: Signer<'info>,
     pub ghost_mint: InterfaceAccount<'info, Mint>,
@@ -1021,49 +4276,72 @@ pub struct RecoveryWithdraw<'info> {
 
 #[derive(Accounts)]
 pub struct UpdateSettings<'info> {
-    // v1.10: seed via ghost.owner so the current owner (post-transfer) can update settings
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    // v1.11: seed via ghost.seed_owner() so the current owner (post-transfer) can update settings
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    #[account(mut)] pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PauseGhost<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     #[account(mut)] pub signer: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct ExpirePause<'info> {
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump)]
+    pub ghost: Box<Account<'info, GhostAccount>>,
+    pub caller: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct AbandonGhost<'info> {
-    // v1.10: seed via ghost.owner; auth still gated by constraint; close-to-signer preserved
-    #[account(mut, seeds = [GHOST_SEED, ghost.owner.as_ref()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized, close = signer)]
+    // v1.11: seed via ghost.seed_owner(); auth still gated by constraint; close-to-signer preserved
+    #[account(mut, seeds = [GHOST_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.bump, constraint = ghost.owner == signer.key() @ GhostError::Unauthorized, close = signer)]
     pub ghost: Box<Account<'info, GhostAccount>>,
     #[account(mut)] pub signer: Signer<'info>,
-    pub ghost_mint: InterfaceAccount<'info, Mint>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK: Vault PDA — emptied to the signer before the ghost closes
+    #[account(mut, seeds = [VAULT_SEED, ghost.seed_owner().as_ref(), ghost.index_seed().as_slice()], bump = ghost.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+    // v1.11: the stake accounts are None for SOL-deposit ghosts (refunded by the close)
+    pub ghost_mint: Option<InterfaceAccount<'info, Mint>>,
     // v1.10: ghost_stake_vault must be authorized by the ghost PDA (the stake vault
     // was init'd with token::authority = ghost in InitializeGhost)
     #[account(mut, token::mint = ghost_mint, token::authority = ghost, token::token_program = token_program)]
-    pub ghost_stake_vault: InterfaceAccount<'info, TokenAccount>,
+    pub ghost_stake_vault: Option<InterfaceAccount<'info, TokenAccount>>,
     // v1.10 HIGH FIX: token::authority = signer prevents malicious frontend from
     // silently swapping the destination ATA on an abandon the user signs.
     #[account(mut, token::mint = ghost_mint, token::authority = signer, token::token_program = token_program)]
-    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
 }
 
-/// MigrateGhost — upgrades account from v1.7 (1220 bytes) to v1.8 (1221 bytes)
-/// The signer must be the ghost owner and must pay for the extra byte via realloc.
+/// MigrateGhost — upgrades account from v1.7 (1220 bytes) / v1.8 (1221 bytes) to v1.11 (2816 bytes)
+/// The signer must be the ghost owner and must pay for the extra bytes via realloc.
 /// system_program required by Anchor for realloc rent-exempt top-up.
 #[derive(Accounts)]
 pub struct MigrateGhost<'info> {
-    // v1.10: seed via ghost.owner so the current owner can migrate post-transfer
-    #[account(
-        mut,
-        seeds = [GHOST_SEED, ghost.owner.as_ref()],
-        bump = ghost.bump,
-        constraint = ghost.owner == signer.key() @ GhostError::Unauthorized,
-    )]
-    pub ghost: Box<Account<'info, GhostAccount>>,
+    // v1.11: raw account — an old layout may not deserialize as the current struct until it
+    // has been realloc'd. Discriminator and owner == signer are checked in the instruction.
+    /// CHECK: program-owned; discriminator and owner field verified in migrate_ghost
+    #[account(mut, owner = crate::ID)]
+    pub ghost: UncheckedAccount<'info>,
     #[account(mut)] pub signer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[event] pub struct GhostRegistered { pub soul: Pubkey, pub interval: i64, pub grace_period: i64, pub recovery_wallets: [Option<Pubkey>; 3], pub staked: u64, pub timestamp: i64 }
 #[event] pub struct HeartbeatReceived { pub soul: Pubkey, pub timestamp: i64, pub ping_number: u64 }
+#[event] pub struct AwakeningStagesSet { pub soul: Pubkey, pub warning_bps: u16, pub final_notice_bps: u16 }
+#[event] pub struct GhostWarned { pub soul: Pubkey, pub silence_duration: i64, pub awakens_at: i64, pub caller: Pubkey }
+#[event] pub struct GhostFinalNotice { pub soul: Pubkey, pub grace_period_ends: i64, pub bounty_paid: u64, pub caller: Pubkey }
 #[event] pub struct GhostAwakened { pub soul: Pubkey, pub silence_duration: i64, pub awakened_at: i64, pub grace_period_ends: i64, pub bounty_paid: u64, pub caller: Pubkey }
 #[event] pub struct AwakeningCancelled { pub soul: Pubkey, pub cancelled_by: Pubkey, pub timestamp: i64 }
 #[event] pub struct LegacyExecuted { pub soul: Pubkey, pub executed_at: i64, pub beneficiary_count: u8 }
@@ -1071,17 +4349,62 @@ pub struct MigrateGhost<'info> {
 #[event] pub struct BeneficiaryAdded { pub soul: Pubkey, pub recipient: Pubkey, pub amount: u64, pub action: u8 }
 #[event] pub struct VaultDeposit { pub soul: Pubkey, pub amount: u64 }
 #[event] pub struct VaultWithdrawal { pub soul: Pubkey, pub amount: u64 }
-#[event] pub struct GhostAbandoned { pub soul: Pubkey, pub burned: u64, pub returned: u64 }
+#[event] pub struct GhostAbandoned { pub soul: Pubkey, pub burned: u64, pub returned: u64, pub burn_bps: u16 }
 #[event] pub struct WholeVaultRecipientSet { pub soul: Pubkey, pub recipient: Option<Pubkey>, pub cleared: bool, pub previous: Option<Pubkey> }
 #[event] pub struct RecoveryWithdrawal { pub soul: Pubkey, pub recovery_wallet: Pubkey, pub amount: u64 }
 #[event] pub struct BeneficiaryUpdated { pub soul: Pubkey, pub index: u8, pub old_recipient: Pubkey, pub new_recipient: Pubkey, pub old_amount: u64, pub new_amount: u64, pub action: u8 }
 #[event] pub struct BeneficiariesCleared { pub soul: Pubkey, pub cleared_by: Pubkey, pub count: u8 }
-#[event] pub struct GhostPaused { pub soul: Pubkey, pub timestamp: i64 }
+#[event] pub struct GhostPaused { pub soul: Pubkey, pub timestamp: i64, pub until: i64 }
+#[event] pub struct GhostPauseLapsed { pub soul: Pubkey, pub paused_until: i64, pub timestamp: i64 }
 #[event] pub struct GhostResumed { pub soul: Pubkey, pub timestamp: i64 }
 #[event] pub struct OwnershipTransferInitiated { pub soul: Pubkey, pub pending_owner: Pubkey, pub timestamp: i64 }
 #[event] pub struct OwnershipTransferAccepted { pub old_owner: Pubkey, pub new_owner: Pubkey, pub timestamp: i64 }
+#[event] pub struct BeneficiaryAccountUpdated { pub soul: Pubkey, pub id: u32, pub old_recipient: Pubkey, pub new_recipient: Pubkey, pub old_amount: u64, pub new_amount: u64, pub action: u8 }
+#[event] pub struct BeneficiaryAccountRemoved { pub soul: Pubkey, pub id: u32 }
+#[event] pub struct BeneficiaryAccountTallied { pub soul: Pubkey, pub id: u32, pub amount: u64, pub pending_tallies: u32 }
+#[event] pub struct GhostIndexed { pub soul: Pubkey, pub ghost: Pubkey, pub ghost_index: u16 }
+#[event] pub struct PingDelegateAdded { pub soul: Pubkey, pub delegate: Pubkey, pub expires_at: i64 }
+#[event] pub struct PingDelegateRemoved { pub soul: Pubkey, pub delegate: Pubkey }
+#[event] pub struct OwnershipTransferCancelled { pub soul: Pubkey, pub pending_owner: Pubkey, pub timestamp: i64 }
 #[event] pub struct BurnExecuted { pub soul: Pubkey, pub mint: Pubkey, pub amount: u64 }
 #[event] pub struct MigrationComplete { pub soul: Pubkey, pub old_size: u16, pub new_size: u16, pub schema_version: u8, pub timestamp: i64 }
+#[event] pub struct SolVaultDeposit { pub soul: Pubkey, pub amount: u64 }
+#[event] pub struct SolVaultWithdrawal { pub soul: Pubkey, pub amount: u64 }
+#[event] pub struct SolTransferExecuted { pub soul: Pubkey, pub recipient: Pubkey, pub amount: u64 }
+#[event] pub struct BeneficiaryVestingSet { pub soul: Pubkey, pub index: u8, pub cliff_seconds: i64, pub duration_seconds: i64, pub tranches: u16 }
+#[event] pub struct VestedClaimed { pub soul: Pubkey, pub index: u8, pub recipient: Pubkey, pub amount: u64, pub claimed: u64, pub entitlement: u64 }
+#[event] pub struct BeneficiaryContingentSet { pub soul: Pubkey, pub index: u8, pub contingent_recipient: Option<Pubkey>, pub after_seconds: i64 }
+#[event] pub struct ContingentTransferExecuted { pub soul: Pubkey, pub index: u8, pub primary: Pubkey, pub contingent: Pubkey, pub amount: u64 }
+#[event] pub struct DisclaimPolicySet { pub soul: Pubkey, pub policy: u8 }
+#[event] pub struct BeneficiaryDisclaimed { pub soul: Pubkey, pub index: u8, pub recipient: Pubkey, pub mint: Option<Pubkey>, pub amount: u64, pub routed_to: u8 }
+#[event] pub struct BeneficiaryShareSet { pub soul: Pubkey, pub index: u8, pub share_bps: u16 }
+#[event] pub struct ProtocolConfigInitialized { pub admin: Pubkey }
+#[event] pub struct ProtocolFeesUpdated { pub previous_execution_fee_bps: u16, pub execution_fee_bps: u16, pub registration_fee_lamports: u64, pub min_stake: u64, pub min_sol_deposit: u64 }
+#[event] pub struct SolDepositLocked { pub soul: Pubkey, pub amount: u64 }
+#[event] pub struct AbandonVaultWithdrawn { pub soul: Pubkey, pub token_accounts_closed: u8, pub lamports: u64 }
+#[event] pub struct SolDepositRefunded { pub soul: Pubkey, pub amount: u64 }
+#[event] pub struct AbandonScheduleUpdated { pub trial_seconds: i64, pub burn_ramp_seconds: i64 }
+#[event] pub struct MaxPauseUpdated { pub max_pause_seconds: i64 }
+#[event] pub struct ProtocolWalletsUpdated { pub fee_wallet: Pubkey, pub bot_ops_wallet: Pubkey }
+#[event] pub struct ProtocolAdminChanged { pub previous: Pubkey, pub admin: Pubkey }
+#[event] pub struct RentRecipientSet { pub soul: Pubkey, pub recipient: Option<Pubkey>, pub previous: Option<Pubkey> }
+#[event] pub struct VaultTokenAccountClosed { pub soul: Pubkey, pub mint: Pubkey, pub rent_recipient: Pubkey }
+#[event] pub struct GhostFinalized { pub soul: Pubkey, pub rent_recipient: Pubkey, pub stake_returned: u64 }
+#[event] pub struct WholeVaultSwept { pub soul: Pubkey, pub recipient: Pubkey, pub mint_count: u8, pub closed_count: u8 }
+#[event] pub struct ExecutionSnapshotTaken { pub soul: Pubkey, pub mint_count: u8, pub taken_at: i64 }
+#[event] pub struct ResidualRecipientSet { pub soul: Pubkey, pub recipient: Option<Pubkey>, pub previous: Option<Pubkey> }
+#[event] pub struct ResidualSwept { pub soul: Pubkey, pub recipient: Pubkey, pub mint: Option<Pubkey>, pub amount: u64 }
+#[event] pub struct GuardianThresholdSet { pub soul: Pubkey, pub threshold: u8, pub guardians: u8 }
+#[event] pub struct GuardianActionProposed { pub soul: Pubkey, pub proposal_id: u64, pub proposer: Pubkey, pub expires_at: i64 }
+#[event] pub struct GuardianActionApproved { pub soul: Pubkey, pub proposal_id: u64, pub guardian: Pubkey, pub approvals: u8 }
+#[event] pub struct GuardianDelaySet { pub soul: Pubkey, pub delay_seconds: i64 }
+#[event] pub struct GuardianActionQueued { pub soul: Pubkey, pub proposal_id: u64, pub executable_at: i64 }
+// proposal_id = None: an owner ping vetoed every queued action (`count` of them)
+#[event] pub struct GuardianActionVetoed { pub soul: Pubkey, pub proposal_id: Option<u64>, pub count: u8, pub timestamp: i64 }
+#[event] pub struct DeathAttested { pub soul: Pubkey, pub proposal_id: u64, pub executed_by: Pubkey, pub attested_at: i64, pub execute_after: i64 }
+#[event] pub struct GuardianActionExecuted { pub soul: Pubkey, pub proposal_id: u64, pub executed_by: Pubkey }
+#[event] pub struct GuardianProposalClosed { pub soul: Pubkey, pub proposal_id: u64, pub executed: bool }
+#[event] pub struct BeneficiaryShareResolved { pub soul: Pubkey, pub index: u8, pub share_bps: u16, pub vault_balance: u64, pub amount: u64 }
 
 #[error_code]
 pub enum GhostError {
@@ -1112,4 +4435,234 @@ pub enum GhostError {
     #[msg("Account is already on the latest schema version.")] AlreadyMigrated,
     // v1.10: appended at the end to preserve existing error codes (Anchor numbers positionally)
     #[msg("Invalid beneficiary action. Must be 0 (Transfer) or 1 (Burn).")] InvalidBeneficiaryAction,
-}
\ No newline at end of file
+    // v1.11: appended — see note above
+    #[msg("Vault does not hold enough distributable SOL (rent reserve excluded).")] InsufficientVaultBalance,
+    #[msg("Account must be migrated to the latest schema first. Call migrate_ghost.")] MigrationRequired,
+    #[msg("Beneficiary shares for this mint would exceed 10,000 bps.")] ShareAllocationExceeded,
+    #[msg("Missing the vault ATA for a beneficiary mint in remaining accounts.")] MissingVaultTokenAccount,
+    #[msg("Too many distinct beneficiary mints for one execution snapshot.")] TooManySnapshotMints,
+    #[msg("Beneficiary mint is not recorded in the execution snapshot.")] MintNotInSnapshot,
+    #[msg("Beneficiary slots are still unpaid.")] BeneficiariesOutstanding,
+    #[msg("A whole vault recipient is set — leftovers go through the whole vault flow.")] WholeVaultRecipientActive,
+    #[msg("Guardian quorum or time-lock is configured. Use propose/approve/execute_guardian_action.")] GuardianProposalRequired,
+    #[msg("Guardian threshold must be between 1 and the number of recovery wallets.")] InvalidGuardianThreshold,
+    #[msg("Invalid guardian action parameters.")] InvalidGuardianAction,
+    #[msg("Guardian proposal has expired.")] ProposalExpired,
+    #[msg("Guardian proposal has already been executed.")] ProposalAlreadyExecuted,
+    #[msg("Guardian proposal is still active.")] ProposalStillActive,
+    #[msg("This recovery wallet has already approved the proposal.")] AlreadyApproved,
+    #[msg("Guardian proposal has not reached quorum.")] QuorumNotReached,
+    #[msg("Recovery wallets changed since the proposal was opened.")] GuardianSetChanged,
+    #[msg("Accounts supplied do not match the guardian proposal.")] ProposalAccountMismatch,
+    #[msg("Guardian delay must be between 0 and 30 days.")] InvalidGuardianDelay,
+    #[msg("Guardian action was vetoed by the owner.")] GuardianActionVetoed,
+    #[msg("Guardian action is still inside the owner veto window.")] GuardianActionTimeLocked,
+    #[msg("New owner must differ from the current owner.")] InvalidNewOwner,
+    #[msg("Ownership transfer cooldown has not elapsed.")] OwnershipTransferCooldown,
+    #[msg("Invalid ping delegate.")] InvalidPingDelegate,
+    #[msg("All ping delegate slots are in use.")] TooManyPingDelegates,
+    #[msg("Signed heartbeat is missing, malformed or not signed by the owner.")] InvalidSignedHeartbeat,
+    #[msg("Signed heartbeat timestamp is too old.")] SignedHeartbeatExpired,
+    #[msg("Beneficiary account has already been tallied.")] BeneficiaryAlreadyTallied,
+    #[msg("Beneficiary account has not been tallied into the execution snapshot.")] BeneficiaryNotTallied,
+    #[msg("Beneficiary accounts are still being tallied. Crank tally_beneficiary_account first.")] BeneficiaryTallyPending,
+    #[msg("Invalid vesting schedule.")] InvalidVestingSchedule,
+    #[msg("This slot vests. Use claim_vested.")] VestingSlot,
+    #[msg("This slot has no vesting schedule.")] NotAVestingSlot,
+    #[msg("Nothing new has vested yet.")] NothingVested,
+    #[msg("Invalid contingent recipient.")] InvalidContingentRecipient,
+    #[msg("This slot has a contingent recipient. The primary must claim with claim_beneficiary.")] ContingentSlot,
+    #[msg("The primary beneficiary's claim window is still open.")] ContingentWindowOpen,
+    #[msg("Invalid disclaim policy.")] InvalidDisclaimPolicy,
+    #[msg("This slot has been disclaimed by its recipient.")] SlotDisclaimed,
+    #[msg("remaining_accounts must be [vault_token_account, mint, recipient_token_account, fee_token_account] groups under token_program.")] InvalidSweepAccounts,
+    #[msg("Too many distinct mints swept for one execution snapshot.")] TooManySweptMints,
+    #[msg("The vault still holds assets or open token accounts.")] VaultNotEmpty,
+    #[msg("Beneficiary accounts must be closed first.")] BeneficiaryAccountsOpen,
+    #[msg("Fee exceeds the protocol hard cap.")] FeeAboveCap,
+    #[msg("Invalid protocol wallet.")] InvalidProtocolWallet,
+    #[msg("This ghost has a $GHOST stake — the stake accounts are required.")] MissingStakeAccounts,
+    #[msg("remaining_accounts must be [vault_token_account, mint, owner_token_account, token_program] groups.")] InvalidWithdrawAccounts,
+    #[msg("Abandon trial must be 0–90 days and the burn ramp non-negative.")] InvalidAbandonSchedule,
+    #[msg("Pause end must be in the future and within the protocol maximum.")] InvalidPauseDuration,
+    #[msg("Stage thresholds must be below 10000 bps.")] InvalidAwakeningStages,
+    #[msg("This awakening stage is not enabled for the ghost.")] AwakeningStageNotEnabled,
+    #[msg("This awakening stage has already been recorded.")] AwakeningStageRecorded,
+    #[msg("This awakening stage is not due yet.")] AwakeningStageNotDue,
+    #[msg("An earlier awakening stage must be recorded first.")] AwakeningStageMissing,
+    #[msg("The beneficiary slot no longer holds the recipient and mint the proposal was approved for.")] BeneficiaryChanged,
+    #[msg("A ghost whose ownership was transferred cannot be abandoned.")] OwnershipTransferred,
+    #[msg("remaining_accounts must be [vault_token_account, token_program] pairs of the vault's token accounts.")] InvalidFinalizeAccounts,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_800_000_000;
+
+    // A freshly registered v1.11 ghost: all-zero Borsh data is a valid GhostAccount
+    // (every Option None), which initialize then fills in.
+    fn ghost(interval_seconds: i64, grace_period_seconds: i64) -> GhostAccount {
+        let data = vec![0u8; GHOST_ACCOUNT_SPACE];
+        let mut ghost = GhostAccount::deserialize(&mut &data[..]).unwrap();
+        ghost.initialize(Pubkey::new_unique(), interval_seconds, grace_period_seconds, MIN_STAKE, 255, 255, NOW, EXECUTION_FEE_BPS as u16);
+        ghost
+    }
+
+    fn config() -> ProtocolConfig {
+        ProtocolConfig {
+            admin: Pubkey::new_unique(),
+            execution_fee_bps: EXECUTION_FEE_BPS as u16,
+            registration_fee_lamports: REGISTRATION_FEE_LAMPORTS,
+            min_stake: MIN_STAKE,
+            fee_wallet: PROTOCOL_FEE_WALLET,
+            bot_ops_wallet: BOT_OPS_WALLET,
+            bump: 255,
+            min_sol_deposit: MIN_SOL_DEPOSIT_LAMPORTS,
+            abandon_trial_seconds: ABANDON_TRIAL_SECONDS,
+            abandon_burn_ramp_seconds: ABANDON_BURN_RAMP_SECONDS,
+            max_pause_seconds: MAX_PAUSE_SECONDS,
+        }
+    }
+
+    #[test]
+    fn ghost_account_space_covers_every_option_populated() {
+        let mut ghost = ghost(86_400, 3_600);
+        let key = Some(Pubkey::new_unique());
+        ghost.recovery_wallets = [key; 3];
+        ghost.awakened_at = Some(NOW);
+        ghost.executed_at = Some(NOW);
+        ghost.whole_vault_recipient = key;
+        ghost.pending_owner = key;
+        ghost.residual_recipient = key;
+        ghost.rent_recipient = key;
+        for i in 0..MAX_BENEFICIARIES {
+            ghost.beneficiaries[i].token_mint = key;
+            ghost.beneficiary_ext[i].contingent_recipient = key;
+        }
+        ghost.account_mints = [AccountMint { mint: key, count: 1 }; MAX_SNAPSHOT_MINTS];
+        let mut data = Vec::new();
+        ghost.serialize(&mut data).unwrap();
+        assert!(data.len() <= GHOST_ACCOUNT_SPACE);
+    }
+
+    #[test]
+    fn split_fee_rounds_down_and_never_overflows() {
+        assert_eq!(split_fee(10_000, 50), (9_950, 50));
+        assert_eq!(split_fee(199, 50), (199, 0));
+        assert_eq!(split_fee(0, 100), (0, 0));
+        let (net, fee) = split_fee(u64::MAX, 100);
+        assert_eq!(fee, u64::MAX / 100);
+        assert_eq!(net + fee, u64::MAX);
+    }
+
+    #[test]
+    fn fee_lock_only_ratchets_down() {
+        let mut ghost = ghost(86_400, 3_600);
+        let mut config = config();
+        ghost.execution_fee_bps = 50;
+        config.execution_fee_bps = 100;
+        assert_eq!(ghost.fee_bps(&config), 50);
+        config.execution_fee_bps = 20;
+        assert_eq!(ghost.fee_bps(&config), 20);
+        config.execution_fee_bps = 100;
+        assert_eq!(ghost.fee_bps(&config), 20);
+        assert_eq!(ghost.execution_fee_bps, 20);
+    }
+
+    #[test]
+    fn stage_bounties_add_up_to_the_silence_bounty_of_one_base() {
+        let mut ghost = ghost(86_400, 3_600);
+        ghost.warning_bps = 5_000;
+        assert_eq!(ghost.awakening_bounty_bps(), SILENCE_BOUNTY_BPS);
+        ghost.final_notice_bps = 5_000;
+        assert_eq!(ghost.awakening_bounty_bps() + FINAL_NOTICE_BOUNTY_BPS, SILENCE_BOUNTY_BPS);
+        // An attested awakening fixes the base the final notice is sliced from
+        ghost.record_attestation(0, NOW + 10);
+        assert_eq!(ghost.stage_bounty_base, MIN_STAKE);
+    }
+
+    #[test]
+    fn abandon_burn_is_zero_in_trial_then_ramps_to_cap() {
+        let ghost = ghost(86_400, 3_600);
+        let config = config();
+        assert_eq!(ghost.abandon_burn_bps(&config, NOW + ABANDON_TRIAL_SECONDS), 0);
+        let half_ramp = NOW + ABANDON_TRIAL_SECONDS + ABANDON_BURN_RAMP_SECONDS / 2;
+        assert_eq!(ghost.abandon_burn_bps(&config, half_ramp), BURN_ON_ABANDON_BPS / 2);
+        let past_ramp = NOW + ABANDON_TRIAL_SECONDS + ABANDON_BURN_RAMP_SECONDS + 1;
+        assert_eq!(ghost.abandon_burn_bps(&config, past_ramp), BURN_ON_ABANDON_BPS);
+    }
+
+    #[test]
+    fn pause_lapses_and_silence_counts_from_its_end() {
+        let mut ghost = ghost(86_400, 3_600);
+        ghost.paused = true;
+        ghost.paused_until = NOW + 10 * 86_400;
+        assert!(ghost.is_paused(NOW + 86_400));
+        assert!(!ghost.is_paused(NOW + 10 * 86_400));
+        assert_eq!(ghost.silence_start(), NOW + 10 * 86_400);
+    }
+
+    #[test]
+    fn attestation_keeps_the_pause_and_waits_for_it() {
+        let mut ghost = ghost(86_400, 3_600);
+        ghost.paused = true;
+        ghost.paused_until = NOW + 10 * 86_400;
+        let execute_after = ghost.record_attestation(0, NOW + 86_400);
+        assert_eq!(execute_after, NOW + 10 * 86_400);
+        assert!(ghost.is_paused(NOW + 86_400));
+        assert!(ghost.is_attested());
+        assert_eq!(ghost.grace_end(), execute_after);
+    }
+
+    #[test]
+    fn only_the_owner_may_cancel_an_attested_awakening() {
+        let mut ghost = ghost(86_400, 3_600);
+        ghost.awakened = true;
+        ghost.awakened_at = Some(NOW + 90_000);
+        assert!(ghost.may_cancel_awakening(false));
+        ghost.record_attestation(0, NOW + 100_000);
+        // A delegate ping or recovery wallet is refused; the owner still can
+        assert!(!ghost.may_cancel_awakening(false));
+        assert!(ghost.may_cancel_awakening(true));
+    }
+
+    #[test]
+    fn beneficiary_mints_are_counted_across_slots_and_accounts() {
+        let mut g = ghost(86_400, 3_600);
+        let mints: Vec<Option<Pubkey>> = (0..MAX_SNAPSHOT_MINTS).map(|_| Some(Pubkey::new_unique())).collect();
+        g.beneficiary_count = 1;
+        g.beneficiaries[0].token_mint = mints[0];
+        for mint in &mints[1..] {
+            assert!(g.distinct_mints_with(*mint, None, None) <= MAX_SNAPSHOT_MINTS);
+            g.track_account_mint(*mint).unwrap();
+        }
+        // Same mint as a slot or account: no new snapshot entry needed
+        assert_eq!(g.distinct_mints_with(mints[0], None, None), MAX_SNAPSHOT_MINTS);
+        assert_eq!(g.distinct_mints_with(mints[5], None, None), MAX_SNAPSHOT_MINTS);
+        // A new mint is one too many, unless the slot or account it replaces frees one
+        assert_eq!(g.distinct_mints_with(None, None, None), MAX_SNAPSHOT_MINTS + 1);
+        assert_eq!(g.distinct_mints_with(None, Some(0), None), MAX_SNAPSHOT_MINTS);
+        assert_eq!(g.distinct_mints_with(None, None, Some(mints[5])), MAX_SNAPSHOT_MINTS);
+        g.track_account_mint(mints[5]).unwrap();
+        assert_eq!(g.distinct_mints_with(None, None, Some(mints[5])), MAX_SNAPSHOT_MINTS + 1);
+        g.untrack_account_mint(mints[5]);
+        g.untrack_account_mint(mints[5]);
+        assert_eq!(g.distinct_mints_with(None, None, None), MAX_SNAPSHOT_MINTS);
+    }
+
+    #[test]
+    fn remove_beneficiary_proposal_is_pinned_to_its_slot() {
+        let mut ghost = ghost(86_400, 3_600);
+        let (a, b, mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Some(Pubkey::new_unique()));
+        ghost.beneficiaries[0] = Beneficiary { recipient: a, amount: 1, token_mint: mint, action: 0, executed: false };
+        ghost.beneficiaries[1] = Beneficiary { recipient: b, amount: 1, token_mint: mint, action: 0, executed: false };
+        ghost.beneficiary_count = 2;
+        assert!(ghost.slot_matches(1, b, mint));
+        ghost.remove_beneficiary_at(0).unwrap();
+        // Slot 1 shifted down — the approved (1, b) no longer matches
+        assert!(!ghost.slot_matches(1, b, mint));
+        assert!(ghost.slot_matches(0, b, mint));
+    }
+}